@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+/// The number of prepared statements a connection keeps by default.
+pub(crate) const DEFAULT_CAPACITY: usize = 32;
+
+/// A least-recently-used cache of prepared statements, keyed by the compiled SQL.
+///
+/// Statements that no longer fit are handed back to the caller, which may need the
+/// connection to free them. Pinned statements are never evicted, even past the capacity.
+#[derive(Debug)]
+pub(crate) struct StatementCache<S> {
+    capacity: usize,
+    /// Each statement with the tick of its last use.
+    entries: HashMap<String, (S, u64)>,
+    /// How many times each SQL string is pinned.
+    pins: HashMap<String, usize>,
+    tick: u64,
+}
+
+impl<S> Default for StatementCache<S> {
+    fn default() -> Self {
+        StatementCache::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl<S> StatementCache<S> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        StatementCache {
+            capacity,
+            entries: HashMap::new(),
+            pins: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the statement for `sql`, marking it as the most recently used.
+    #[cfg(any(feature = "mysql", feature = "postgres", test))]
+    pub(crate) fn get(&mut self, sql: &str) -> Option<&S> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(sql).map(|entry| {
+            entry.1 = tick;
+            &entry.0
+        })
+    }
+
+    /// Removes the statement for `sql`, for exclusive use until it is inserted again.
+    pub(crate) fn take(&mut self, sql: &str) -> Option<S> {
+        self.entries.remove(sql).map(|(statement, _)| statement)
+    }
+
+    /// Adds a statement and returns the one it displaced: the previous statement for `sql`,
+    /// the least recently used one if the cache is full, or `statement` itself if the capacity is 0
+    /// and it is not pinned.
+    pub(crate) fn insert(&mut self, sql: String, statement: S) -> Option<S> {
+        if self.capacity == 0 && !self.pins.contains_key(&sql) {
+            return Some(statement);
+        }
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(&sql) {
+            return Some(std::mem::replace(entry, (statement, self.tick)).0);
+        }
+        let evicted = if self.entries.len() >= self.capacity {
+            self.pop_lru()
+        } else {
+            None
+        };
+        self.entries.insert(sql, (statement, self.tick));
+        evicted
+    }
+
+    /// Changes the capacity and returns the statements that no longer fit.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) -> Vec<S> {
+        self.capacity = capacity;
+        let mut evicted = Vec::new();
+        while self.entries.len() > capacity {
+            match self.pop_lru() {
+                Some(statement) => evicted.push(statement),
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    /// Keeps the statement for `sql` from being evicted until it is unpinned as often.
+    pub(crate) fn pin(&mut self, sql: &str) {
+        *self.pins.entry(sql.to_string()).or_insert(0) += 1;
+    }
+
+    /// Releases a pin and returns the statements that no longer fit.
+    pub(crate) fn unpin(&mut self, sql: &str) -> Vec<S> {
+        if let Some(count) = self.pins.get_mut(sql) {
+            *count -= 1;
+            if *count == 0 {
+                self.pins.remove(sql);
+            }
+        }
+        self.set_capacity(self.capacity)
+    }
+
+    /// Removes and returns every statement, pinned or not.
+    pub(crate) fn clear(&mut self) -> Vec<S> {
+        self.entries
+            .drain()
+            .map(|(_, (statement, _))| statement)
+            .collect()
+    }
+
+    fn pop_lru(&mut self) -> Option<S> {
+        let sql = self
+            .entries
+            .iter()
+            .filter(|(sql, _)| !self.pins.contains_key(*sql))
+            .min_by_key(|(_, (_, tick))| *tick)
+            .map(|(sql, _)| sql.clone())?;
+        self.take(&sql)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru() {
+        let mut cache = StatementCache::new(2);
+        assert_eq!(cache.insert("a".into(), 1), None);
+        assert_eq!(cache.insert("b".into(), 2), None);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.insert("c".into(), 3), Some(2));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.insert("a".into(), 4), Some(1));
+        assert_eq!(cache.take("a"), Some(4));
+        assert_eq!(cache.take("a"), None);
+
+        cache.insert("a".into(), 1);
+        cache.get("c");
+        assert_eq!(cache.set_capacity(1), [1]);
+        assert_eq!(cache.clear(), [3]);
+        assert!(cache.set_capacity(0).is_empty());
+        assert_eq!(cache.insert("a".into(), 1), Some(1));
+        assert_eq!(cache.get("a"), None);
+    }
+
+    #[test]
+    fn pin() {
+        let mut cache = StatementCache::new(1);
+        cache.pin("a");
+        assert_eq!(cache.insert("a".into(), 1), None);
+        assert_eq!(cache.insert("b".into(), 2), None);
+        assert_eq!(cache.insert("c".into(), 3), Some(2));
+        assert_eq!(cache.set_capacity(0), [3]);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.insert("a".into(), 4), Some(1));
+        assert_eq!(cache.unpin("a"), [4]);
+
+        cache.pin("a");
+        assert_eq!(cache.insert("a".into(), 1), None);
+        assert_eq!(cache.clear(), [1]);
+        assert_eq!(cache.insert("a".into(), 2), None);
+        assert_eq!(cache.get("a"), Some(&2));
+    }
+}
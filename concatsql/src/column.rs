@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::iter::FromIterator;
+use std::ops::Deref;
+
+/// Metadata of a result column, as reported by the database.
+///
+/// Every field except the name is optional, since not all databases
+/// (or all expressions) report it.
+///
+/// ```
+/// # use concatsql::prelude::*;
+/// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+/// conn.execute("CREATE TABLE items (price DECIMAL(10, 2) NOT NULL)").unwrap();
+/// conn.execute("INSERT INTO items VALUES (1.5)").unwrap();
+/// let rows = conn.rows("SELECT price AS p, 1 FROM items").unwrap();
+/// let columns = rows[0].columns();
+/// assert_eq!(columns[0].name(), "p");
+/// assert_eq!(columns[0].decl_type(), Some("DECIMAL(10, 2)"));
+/// assert_eq!(columns[0].nullable(), Some(false));
+/// assert_eq!(columns[0].table(), Some("items"));
+/// assert_eq!(columns[0].origin_name(), Some("price"));
+/// assert_eq!((columns[0].precision(), columns[0].scale()), (Some(10), Some(2)));
+/// assert_eq!(columns[1].decl_type(), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Column {
+    pub(crate) name: String,
+    pub(crate) decl_type: Option<String>,
+    pub(crate) nullable: Option<bool>,
+    pub(crate) table: Option<String>,
+    pub(crate) origin_name: Option<String>,
+    pub(crate) precision: Option<u32>,
+    pub(crate) scale: Option<u32>,
+}
+
+impl Column {
+    #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres", test))]
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Column {
+            name: name.into(),
+            ..Column::default()
+        }
+    }
+
+    /// The name of the column in the result, after any `AS` alias.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The declared type, e.g. `VARCHAR(255)` (SQLite), `DECIMAL` (MySQL) or `int4` (PostgreSQL).
+    ///
+    /// `None` for expressions on SQLite, which have no declared type.
+    #[inline]
+    pub fn decl_type(&self) -> Option<&str> {
+        self.decl_type.as_deref()
+    }
+
+    /// Whether the column may hold `NULL`, if known.
+    ///
+    /// PostgreSQL does not report nullability.
+    #[inline]
+    pub fn nullable(&self) -> Option<bool> {
+        self.nullable
+    }
+
+    /// The table the column was read from, if it is a plain column reference.
+    ///
+    /// PostgreSQL only reports the table OID, so the name is looked up once per table
+    /// and kept until [`Connection::clear_statement_cache`](./struct.Connection.html#method.clear_statement_cache).
+    #[inline]
+    pub fn table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    /// The name of the column in its table, before any `AS` alias.
+    #[inline]
+    pub fn origin_name(&self) -> Option<&str> {
+        self.origin_name.as_deref()
+    }
+
+    /// The number of significant digits of a `DECIMAL`/`NUMERIC` column.
+    #[inline]
+    pub fn precision(&self) -> Option<u32> {
+        self.precision
+    }
+
+    /// The number of digits after the decimal point of a `DECIMAL`/`NUMERIC` column.
+    #[inline]
+    pub fn scale(&self) -> Option<u32> {
+        self.scale
+    }
+}
+
+/// The columns of a result, shared by all of its rows, with the position of each name.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Columns {
+    columns: Vec<Column>,
+    /// The first column with each name.
+    positions: HashMap<String, usize>,
+}
+
+impl Columns {
+    pub(crate) fn position(&self, name: &str) -> Option<usize> {
+        self.positions.get(name).copied()
+    }
+}
+
+impl FromIterator<Column> for Columns {
+    fn from_iter<I: IntoIterator<Item = Column>>(iter: I) -> Self {
+        let columns: Vec<Column> = iter.into_iter().collect();
+        let mut positions = HashMap::with_capacity(columns.len());
+        for (index, column) in columns.iter().enumerate() {
+            positions.entry(column.name.clone()).or_insert(index);
+        }
+        Columns { columns, positions }
+    }
+}
+
+impl Deref for Columns {
+    type Target = [Column];
+
+    fn deref(&self) -> &[Column] {
+        &self.columns
+    }
+}
+
+/// Parses the `(precision, scale)` of a declared type such as `DECIMAL(10, 2)`.
+#[cfg(feature = "sqlite")]
+pub(crate) fn parse_precision(decl_type: &str) -> (Option<u32>, Option<u32>) {
+    let upper = decl_type.to_ascii_uppercase();
+    if !upper.starts_with("DEC") && !upper.starts_with("NUMERIC") {
+        return (None, None);
+    }
+    let args = match (decl_type.find('('), decl_type.rfind(')')) {
+        (Some(start), Some(end)) if start < end => &decl_type[start + 1..end],
+        _ => return (None, None),
+    };
+    let mut args = args.split(',').map(|arg| arg.trim().parse().ok());
+    match (args.next().flatten(), args.next(), args.next()) {
+        (Some(precision), None, None) => (Some(precision), None),
+        (Some(precision), Some(Some(scale)), None) => (Some(precision), Some(scale)),
+        _ => (None, None),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "sqlite")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precision() {
+        assert_eq!(parse_precision("DECIMAL(10,2)"), (Some(10), Some(2)));
+        assert_eq!(parse_precision("NUMERIC( 5 )"), (Some(5), None));
+        assert_eq!(parse_precision("VARCHAR(255)"), (None, None));
+        assert_eq!(parse_precision("INTEGER"), (None, None));
+        assert_eq!(parse_precision("DECIMAL(a,2)"), (None, None));
+        assert_eq!(parse_precision("DECIMAL(1,2,3)"), (None, None));
+    }
+}
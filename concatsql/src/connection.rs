@@ -2,37 +2,90 @@ use std::borrow::Cow;
 use std::cell::Cell;
 use std::fmt;
 
-use crate::row::Row;
-use crate::value::Value;
+use crate::column::Column;
+use crate::result_set::{ExecResult, ResultSet};
+use crate::row::{FromRow, FromSql, Row};
+use crate::statement::Statement;
+use crate::transaction::{Transaction, TransactionBuilder};
+use crate::value::{ToValue, Value};
 use crate::wrapstring::{IntoWrapString, WrapString};
-use crate::ErrorLevel;
 use crate::Result;
+use crate::{Error, ErrorLevel};
 
 #[allow(clippy::type_complexity)]
 pub(crate) trait ConcatsqlConn {
+    /// Runs `query`, as a batch if it has no parameters, unless `prepared` asks for the
+    /// prepared statement of a [`Statement`](./struct.Statement.html) regardless.
     fn execute_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &crate::ErrorLevel,
-    ) -> Result<()>;
+        prepared: bool,
+    ) -> Result<ExecResult>;
     fn iterate_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &crate::ErrorLevel,
-        callback: &mut dyn FnMut(&[(&str, Option<&str>)]) -> bool,
+        callback: &mut dyn FnMut(&Row) -> bool,
     ) -> Result<()>;
-    fn rows_inner<'a, 'r>(
+    fn rows_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &crate::ErrorLevel,
-    ) -> Result<Vec<Row<'r>>>;
+        prepared: bool,
+    ) -> Result<Vec<Row>>;
+    fn query_iter_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &crate::ErrorLevel,
+        prepared: bool,
+    ) -> Result<Box<dyn QueryRows + 'c>>;
+    fn result_sets_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &crate::ErrorLevel,
+    ) -> Result<Box<dyn Iterator<Item = Result<ResultSet>> + 'c>>;
+    /// Runs `query` once per parameter set and returns the total number of rows affected.
+    fn execute_many_inner<'a>(
+        &self,
+        query: &str,
+        params: &mut dyn Iterator<Item = Vec<Value<'a>>>,
+        error_level: &crate::ErrorLevel,
+    ) -> Result<u64>;
+    /// Prepares `query` into the statement cache and returns its number of placeholders.
+    fn prepare_inner(&self, query: &str, error_level: &crate::ErrorLevel) -> Result<usize>;
+    /// Keeps the prepared statement for `query` cached until it is unpinned.
+    fn pin_statement(&self, query: &str);
+    fn unpin_statement(&self, query: &str);
+    fn set_statement_cache_capacity(&self, capacity: usize);
+    fn clear_statement_cache(&self);
     fn close(&self);
     fn kind(&self) -> ConnKind;
 }
 
+/// The rows of a query, read by a backend as the iterator advances.
+pub(crate) trait QueryRows: Iterator<Item = Result<Row>> {
+    /// The columns of the result, or none if the query failed.
+    fn columns(&mut self) -> &[Column];
+}
+
+impl QueryRows for std::iter::Empty<Result<Row>> {
+    fn columns(&mut self) -> &[Column] {
+        &[]
+    }
+}
+
+impl QueryRows for std::iter::Once<Result<Row>> {
+    fn columns(&mut self) -> &[Column] {
+        &[]
+    }
+}
+
 #[doc(hidden)]
 pub enum ConnKind {
     #[cfg(feature = "sqlite")]
@@ -47,6 +100,7 @@ pub enum ConnKind {
 pub struct Connection {
     pub(crate) conn: Box<dyn ConcatsqlConn>,
     pub(crate) error_level: Cell<ErrorLevel>,
+    pub(crate) in_transaction: Cell<bool>,
 }
 
 unsafe impl Send for Connection {}
@@ -67,6 +121,11 @@ impl fmt::Debug for Connection {
     }
 }
 
+// Without a backend feature `ConnKind` has no variants, so the code after `kind()` is unreachable.
+#[cfg_attr(
+    not(any(feature = "sqlite", feature = "mysql", feature = "postgres")),
+    allow(unreachable_code, unused_mut, unused_variables)
+)]
 impl<'a> Connection {
     /// Execute a statement without processing the resulting rows if any.
     ///
@@ -84,10 +143,35 @@ impl<'a> Connection {
     /// ```
     #[inline]
     pub fn execute<T: IntoWrapString<'a>>(&self, query: T) -> Result<()> {
+        self.execute_with_result(query).map(|_| ())
+    }
+
+    /// Execute a statement and returns the number of rows it changed and the id it generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)").unwrap();
+    /// let result = conn.execute_with_result("INSERT INTO users (name) VALUES ('Alice')").unwrap();
+    /// assert_eq!(result.rows_affected(), 1);
+    /// assert_eq!(result.last_insert_id(), Some(1));
+    ///
+    /// let name = "Bob";
+    /// let result = conn
+    ///     .execute_with_result(query!("UPDATE users SET name = {name} WHERE id = 2"))
+    ///     .unwrap();
+    /// assert_eq!(result.rows_affected(), 0);
+    /// assert_eq!(result.last_insert_id(), None);
+    /// ```
+    #[inline]
+    pub fn execute_with_result<T: IntoWrapString<'a>>(&self, query: T) -> Result<ExecResult> {
         self.conn.execute_inner(
             query.compile(self.conn.kind()),
             query.params(),
             &self.error_level.get(),
+            false,
         )
     }
 
@@ -117,6 +201,39 @@ impl<'a> Connection {
     pub fn iterate<T: IntoWrapString<'a>, F>(&self, query: T, mut callback: F) -> Result<()>
     where
         F: FnMut(&[(&str, Option<&str>)]) -> bool,
+    {
+        self.iterate_rows(query, |row| {
+            let pairs: Vec<(&str, Option<&str>)> = (0..row.column_count())
+                .map(|index| (row.column(index), row.get(index)))
+                .collect();
+            callback(&pairs)
+        })
+    }
+
+    /// Execute a statement and process the resulting rows.
+    ///
+    /// The callback is triggered for each row, as it is read from the database.
+    /// If the callback returns `false`, no more rows will be processed and `Ok(())` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # let stmt = r#"CREATE TABLE users (name TEXT, id INTEGER);
+    /// #               INSERT INTO users (name, id) VALUES ('Alice', 42);
+    /// #               INSERT INTO users (name, id) VALUES ('Bob', 69);"#;
+    /// # conn.execute(stmt).unwrap();
+    /// let mut names = Vec::new();
+    /// conn.iterate_rows("SELECT name FROM users ORDER BY id", |row| {
+    ///     names.push(row.get("name").unwrap().to_string());
+    ///     false
+    /// }).unwrap();
+    /// assert_eq!(names, ["Alice"]);
+    /// ```
+    #[inline]
+    pub fn iterate_rows<T: IntoWrapString<'a>, F>(&self, query: T, mut callback: F) -> Result<()>
+    where
+        F: FnMut(&Row) -> bool,
     {
         self.conn.iterate_inner(
             query.compile(self.conn.kind()),
@@ -128,6 +245,9 @@ impl<'a> Connection {
 
     /// Execute a statement and returns the rows.
     ///
+    /// Each row carries the columns of the result. To read them when there may be no rows,
+    /// use [`query_iter`](#method.query_iter) and [`QueryIter::columns`](./struct.QueryIter.html#method.columns).
+    ///
     /// # Examples
     ///
     /// ```
@@ -144,14 +264,360 @@ impl<'a> Connection {
     /// }
     /// ```
     #[inline]
-    pub fn rows<'r, T: IntoWrapString<'a>>(&self, query: T) -> Result<Vec<Row<'r>>> {
+    pub fn rows<T: IntoWrapString<'a>>(&self, query: T) -> Result<Vec<Row>> {
         self.conn.rows_inner(
             query.compile(self.conn.kind()),
             query.params(),
             &self.error_level.get(),
+            false,
         )
     }
 
+    /// Execute a statement and returns a lazy iterator over the rows.
+    ///
+    /// Rows are fetched from the database one at a time as the iterator advances,
+    /// so the result does not need to fit in memory. On MySQL and PostgreSQL the iterator
+    /// holds the connection until it runs out of rows or is dropped, and any other query
+    /// on the connection meanwhile returns an error. SQLite runs other queries as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # let stmt = r#"CREATE TABLE users (name TEXT, id INTEGER);
+    /// #               INSERT INTO users (name, id) VALUES ('Alice', 42);
+    /// #               INSERT INTO users (name, id) VALUES ('Bob', 69);"#;
+    /// # conn.execute(stmt).unwrap();
+    /// for row in conn.query_iter("SELECT name FROM users") {
+    ///     let row = row.unwrap();
+    ///     println!("name: {}", row.get("name").unwrap_or("NULL"));
+    /// }
+    /// ```
+    pub fn query_iter<'c>(&'c self, query: impl IntoWrapString<'a>) -> QueryIter<'c> {
+        let inner = self
+            .conn
+            .query_iter_inner(
+                query.compile(self.conn.kind()),
+                query.params(),
+                &self.error_level.get(),
+                false,
+            )
+            .unwrap_or_else(|e| Box::new(std::iter::once(Err(e))));
+        QueryIter { inner }
+    }
+
+    /// Execute a batch of statements and returns the result of each of them.
+    ///
+    /// Every statement produces a [`ResultSet`](./struct.ResultSet.html), including those that return no rows.
+    /// With parameters, PostgreSQL and MySQL only accept a single statement.
+    /// Without them, PostgreSQL runs the batch over its simple query protocol, which returns
+    /// every value as [`Value::Text`](./enum.Value.html) and only the name of each column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// let sets = conn.result_sets(r#"
+    ///     CREATE TABLE users (name TEXT, id INTEGER);
+    ///     INSERT INTO users (name, id) VALUES ('Alice', 42), ('Bob', 69);
+    ///     SELECT name FROM users ORDER BY id;
+    /// "#).unwrap();
+    /// assert_eq!(sets.len(), 3);
+    /// assert_eq!(sets[1].rows_affected(), 2);
+    /// assert_eq!(sets[2].columns()[0].name(), "name");
+    /// assert_eq!(sets[2].rows()[1].get("name"), Some("Bob"));
+    /// ```
+    pub fn result_sets(&self, query: impl IntoWrapString<'a>) -> Result<Vec<ResultSet>> {
+        self.result_sets_iter(query).collect()
+    }
+
+    /// Execute a batch of statements and returns a lazy iterator over their results.
+    ///
+    /// On SQLite, each statement runs when the iterator reaches it, and other queries can run
+    /// in between. On MySQL, the results are read as the iterator reaches them, and any other
+    /// query on the connection returns an error until the iterator is finished or dropped.
+    /// PostgreSQL runs the whole batch and reads every result before this returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// let mut sets = conn.result_sets_iter("SELECT 1; SELECT 2, 3;");
+    /// assert_eq!(sets.next().unwrap().unwrap().rows()[0].get(0), Some("1"));
+    /// assert_eq!(sets.next().unwrap().unwrap().columns().len(), 2);
+    /// assert!(sets.next().is_none());
+    /// ```
+    pub fn result_sets_iter<'c>(&'c self, query: impl IntoWrapString<'a>) -> ResultSets<'c> {
+        let inner = self
+            .conn
+            .result_sets_inner(
+                query.compile(self.conn.kind()),
+                query.params(),
+                &self.error_level.get(),
+            )
+            .unwrap_or_else(|e| Box::new(std::iter::once(Err(e))));
+        ResultSets { inner }
+    }
+
+    /// Execute a statement and converts the rows with [FromRow](./trait.FromRow.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # let stmt = r#"CREATE TABLE users (name TEXT, id INTEGER);
+    /// #               INSERT INTO users (name, id) VALUES ('Alice', 42);
+    /// #               INSERT INTO users (name, id) VALUES ('Bob', 69);"#;
+    /// # conn.execute(stmt).unwrap();
+    /// #[derive(FromRow)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String,
+    /// }
+    ///
+    /// let users = conn.rows_as::<User>("SELECT * FROM users ORDER BY id").unwrap();
+    /// assert_eq!(users[0].id, 42);
+    /// assert_eq!(users[1].name, "Bob");
+    /// ```
+    pub fn rows_as<T: FromRow>(&self, query: impl IntoWrapString<'a>) -> Result<Vec<T>> {
+        self.rows(query)?.iter().map(T::from_row).collect()
+    }
+
+    /// Execute a statement and returns the first column of the first row.
+    ///
+    /// Returns [Error::NoRows](./enum.Error.html#variant.NoRows) if the query returned no rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # let stmt = r#"CREATE TABLE users (name TEXT, id INTEGER);
+    /// #               INSERT INTO users (name, id) VALUES ('Alice', 42);
+    /// #               INSERT INTO users (name, id) VALUES ('Bob', 69);"#;
+    /// # conn.execute(stmt).unwrap();
+    /// let count = conn.query_scalar::<i64>("SELECT COUNT(*) FROM users").unwrap();
+    /// assert_eq!(count, 2);
+    /// let max = conn.query_scalar::<Option<i64>>("SELECT MAX(id) FROM users WHERE id < 0").unwrap();
+    /// assert_eq!(max, None);
+    /// ```
+    pub fn query_scalar<T: FromSql>(&self, query: impl IntoWrapString<'a>) -> Result<T> {
+        match self.rows(query)?.first() {
+            Some(row) => row.get_into(0),
+            None => Err(Error::NoRows),
+        }
+    }
+
+    /// Execute a statement that must return exactly one row.
+    ///
+    /// Returns [Error::NoRows](./enum.Error.html#variant.NoRows) if the query returned no rows
+    /// and [Error::TooManyRows](./enum.Error.html#variant.TooManyRows) if it returned more than one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # use concatsql::Error;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # let stmt = r#"CREATE TABLE users (name TEXT, id INTEGER);
+    /// #               INSERT INTO users (name, id) VALUES ('Alice', 42);
+    /// #               INSERT INTO users (name, id) VALUES ('Bob', 69);"#;
+    /// # conn.execute(stmt).unwrap();
+    /// let row = conn.query_one("SELECT name FROM users WHERE id = 42").unwrap();
+    /// assert_eq!(row.get("name"), Some("Alice"));
+    /// assert_eq!(conn.query_one("SELECT name FROM users WHERE id = 0"), Err(Error::NoRows));
+    /// assert_eq!(conn.query_one("SELECT name FROM users"), Err(Error::TooManyRows));
+    /// ```
+    pub fn query_one(&self, query: impl IntoWrapString<'a>) -> Result<Row> {
+        self.query_opt(query)?.ok_or(Error::NoRows)
+    }
+
+    /// Execute a statement that must return at most one row.
+    ///
+    /// Returns [Error::TooManyRows](./enum.Error.html#variant.TooManyRows) if it returned more than one.
+    /// Only the first two rows are fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # use concatsql::Error;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # let stmt = r#"CREATE TABLE users (name TEXT, id INTEGER);
+    /// #               INSERT INTO users (name, id) VALUES ('Alice', 42);
+    /// #               INSERT INTO users (name, id) VALUES ('Bob', 69);"#;
+    /// # conn.execute(stmt).unwrap();
+    /// let row = conn.query_opt("SELECT name FROM users WHERE id = 42").unwrap();
+    /// assert_eq!(row.unwrap().get("name"), Some("Alice"));
+    /// assert_eq!(conn.query_opt("SELECT name FROM users WHERE id = 0"), Ok(None));
+    /// assert_eq!(conn.query_opt("SELECT name FROM users"), Err(Error::TooManyRows));
+    /// ```
+    pub fn query_opt(&self, query: impl IntoWrapString<'a>) -> Result<Option<Row>> {
+        let mut rows = self.query_iter(query);
+        let row = match rows.next() {
+            Some(row) => row?,
+            None => return Ok(None),
+        };
+        match rows.next() {
+            Some(Ok(_)) => Err(Error::TooManyRows),
+            Some(Err(e)) => Err(e),
+            None => Ok(Some(row)),
+        }
+    }
+
+    /// Runs one statement for each parameter set and returns the total number of rows affected.
+    ///
+    /// The statement is prepared once and bound again for every set, whose length must
+    /// match its placeholders. With `transaction`, the sets run in a new transaction that
+    /// is rolled back if any of them fails, whatever the error level, so that either all of
+    /// them or none are applied; this fails if a [`Transaction`](./struct.Transaction.html)
+    /// is already in progress. Otherwise each set is applied as it runs, as part of the
+    /// current transaction if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// conn.execute("CREATE TABLE users (name TEXT, age INTEGER)").unwrap();
+    ///
+    /// let users = [("Alice".to_string(), 42), ("Bob".to_string(), 69)];
+    /// let rows = users
+    ///     .iter()
+    ///     .map(|(name, age)| vec![name as &dyn ToValue, age]);
+    /// let count = conn
+    ///     .execute_many("INSERT INTO users VALUES (?, ?)", rows, true)
+    ///     .unwrap();
+    /// assert_eq!(count, 2);
+    ///
+    /// let (name, age) = ("", 0);
+    /// let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+    /// conn.execute_many(&sql, vec![params!["Carol", 50], params!["Dave", 20]], false)
+    ///     .unwrap();
+    /// ```
+    pub fn execute_many<'p, 'r, I, P>(
+        &self,
+        query: impl IntoWrapString<'a>,
+        params: I,
+        transaction: bool,
+    ) -> Result<u64>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[&'r dyn ToValue<'p>]>,
+        'p: 'r,
+    {
+        let query = query.compile(self.conn.kind());
+        let mut params = params
+            .into_iter()
+            .map(|row| row.as_ref().iter().map(|param| param.to_value()).collect());
+        let error_level = self.error_level.get();
+        if !transaction {
+            return self
+                .conn
+                .execute_many_inner(&query, &mut params, &error_level);
+        }
+
+        let transaction = self.transaction()?;
+        // A failed set must be reported to be rolled back, even if errors are ignored.
+        let strict = match error_level {
+            ErrorLevel::AlwaysOk => ErrorLevel::Release,
+            error_level => error_level,
+        };
+        match self.conn.execute_many_inner(&query, &mut params, &strict) {
+            Ok(total) => transaction.commit().map(|_| total),
+            Err(e) => {
+                transaction.rollback()?;
+                match error_level {
+                    ErrorLevel::AlwaysOk => Ok(0),
+                    _ => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Prepares a statement to be run many times with different parameters.
+    ///
+    /// The values of a `query!` only serve to place the placeholders and are not kept.
+    /// See [`Statement`](./struct.Statement.html) for an example.
+    pub fn prepare(&self, query: impl IntoWrapString<'a>) -> Result<Statement<'_>> {
+        let query = query.compile(self.conn.kind()).into_owned();
+        // Pinned before it is prepared, so that a cache without room still keeps it.
+        let mut statement = Statement::new(self, query);
+        statement.param_count = self
+            .conn
+            .prepare_inner(&statement.query, &self.error_level.get())?;
+        Ok(statement)
+    }
+
+    /// Begins a transaction, which is rolled back unless committed.
+    ///
+    /// See [`Transaction`](./struct.Transaction.html) for an example.
+    #[inline]
+    pub fn transaction(&self) -> Result<Transaction<'_>> {
+        self.build_transaction().start()
+    }
+
+    /// Configures a transaction before beginning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::IsolationLevel;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # conn.execute("CREATE TABLE users (name TEXT)").unwrap();
+    /// let tx = conn
+    ///     .build_transaction()
+    ///     .isolation_level(IsolationLevel::Serializable)
+    ///     .read_only(true)
+    ///     .start()
+    ///     .unwrap();
+    /// assert!(tx.execute("INSERT INTO users VALUES ('Alice')").is_err());
+    /// ```
+    #[inline]
+    pub fn build_transaction(&self) -> TransactionBuilder<'_> {
+        TransactionBuilder::new(self)
+    }
+
+    /// Runs a statement built by this crate, which has no parameters.
+    ///
+    /// Errors are reported even with `ErrorLevel::AlwaysOk`, since the caller acts on them.
+    pub(crate) fn execute_raw(&self, query: impl Into<Cow<'static, str>>) -> Result<()> {
+        let error_level = match self.error_level.get() {
+            ErrorLevel::AlwaysOk => ErrorLevel::Release,
+            error_level => error_level,
+        };
+        self.conn
+            .execute_inner(query.into(), &[], &error_level, false)
+            .map(|_| ())
+    }
+
+    /// Sets how many prepared statements are kept for reuse, 32 by default.
+    ///
+    /// Statements are cached by their SQL, which `query!` keeps identical between calls
+    /// whatever the parameters. A capacity of 0 disables the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// conn.set_statement_cache_capacity(128);
+    /// ```
+    pub fn set_statement_cache_capacity(&self, capacity: usize) {
+        self.conn.set_statement_cache_capacity(capacity);
+    }
+
+    /// Drops every cached prepared statement.
+    ///
+    /// PostgreSQL rejects a cached statement whose result columns changed, so clear
+    /// the cache after altering the schema. MySQL and SQLite prepare such statements again.
+    pub fn clear_statement_cache(&self) {
+        self.conn.clear_statement_cache();
+    }
+
     /// Sets the error level.  
     /// The default value is [ErrorLevel](./enum.ErrorLevel.html)::Develop for debug builds and [ErrorLevel](./enum.ErrorLevel.html)::Release for release builds.
     ///
@@ -173,6 +639,64 @@ impl Drop for Connection {
     }
 }
 
+/// A lazy iterator over the rows of a query, returned by [`Connection::query_iter`].
+pub struct QueryIter<'c> {
+    pub(crate) inner: Box<dyn QueryRows + 'c>,
+}
+
+impl QueryIter<'_> {
+    /// The columns of the result, known even when there are no rows.
+    ///
+    /// SQLite reads the first row to find them if the iterator has not done so yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// # conn.execute("CREATE TABLE users (name TEXT, id INTEGER)").unwrap();
+    /// let mut rows = conn.query_iter("SELECT name, id FROM users");
+    /// assert_eq!(rows.columns()[1].name(), "id");
+    /// assert!(rows.next().is_none());
+    /// ```
+    pub fn columns(&mut self) -> &[Column] {
+        self.inner.columns()
+    }
+}
+
+impl<'c> Iterator for QueryIter<'c> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl fmt::Debug for QueryIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryIter").finish_non_exhaustive()
+    }
+}
+
+/// A lazy iterator over the results of a batch, returned by [`Connection::result_sets_iter`].
+pub struct ResultSets<'c> {
+    inner: Box<dyn Iterator<Item = Result<ResultSet>> + 'c>,
+}
+
+impl Iterator for ResultSets<'_> {
+    type Item = Result<ResultSet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl fmt::Debug for ResultSets<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultSets").finish_non_exhaustive()
+    }
+}
+
 /// Does not escape.
 ///
 /// Don't use if the value entered is unreliable (e.g. entered by user).  
@@ -197,6 +721,6 @@ impl Drop for Connection {
 ///
 /// - Use trusted values
 /// - Use in an environment where SQL injection does not occur
-pub unsafe fn without_escape<T: ?Sized + ToString>(query: &T) -> WrapString {
+pub unsafe fn without_escape<T: ?Sized + ToString>(query: &T) -> WrapString<'static> {
     WrapString::new(query)
 }
@@ -11,6 +11,12 @@ pub enum Error {
     ParseError,
     /// Return value when [get_into](./struct.Row.html#method.get_into) method fails.
     ColumnNotFound,
+    /// The column is NULL but the target type is not an `Option`.
+    UnexpectedNull(String),
+    /// The query returned no rows.
+    NoRows,
+    /// The query returned more than one row where at most one was expected.
+    TooManyRows,
 }
 
 /// Change the output error message.
@@ -72,6 +78,9 @@ impl fmt::Display for Error {
                 Error::AnyError => String::from("AnyError"),
                 Error::ParseError => String::from("ParseError"),
                 Error::ColumnNotFound => String::from("ColumnNotFound"),
+                Error::UnexpectedNull(column) => format!("UnexpectedNull: {}", column),
+                Error::NoRows => String::from("NoRows"),
+                Error::TooManyRows => String::from("TooManyRows"),
             }
         )
     }
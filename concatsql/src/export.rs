@@ -0,0 +1,467 @@
+//! Write query results as CSV, JSON Lines or a plain-text table.
+//!
+//! ```
+//! # use concatsql::prelude::*;
+//! use concatsql::export::Export;
+//!
+//! # let conn = concatsql::sqlite::open(":memory:").unwrap();
+//! conn.execute(r#"
+//!     CREATE TABLE users (name TEXT, age INTEGER, password TEXT);
+//!     INSERT INTO users VALUES ('Alice', 42, 'secret'), ('Bob, Jr.', NULL, 'hunter2');
+//! "#).unwrap();
+//!
+//! let mut out = Vec::new();
+//! Export::csv()
+//!     .null("NULL")
+//!     .mask("password")
+//!     .write(&conn, "SELECT * FROM users", &mut out)
+//!     .unwrap();
+//! assert_eq!(
+//!     String::from_utf8(out).unwrap(),
+//!     "name,age,password\r\nAlice,42,***\r\n\"Bob, Jr.\",NULL,***\r\n"
+//! );
+//! ```
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::row::Row;
+use crate::value::Value;
+use crate::wrapstring::IntoWrapString;
+use crate::Result;
+
+/// The output format of an [`Export`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Comma-separated values as described by RFC 4180, with CRLF line breaks.
+    Csv,
+    /// One JSON object per line, keyed by column name.
+    JsonLines,
+    /// Columns padded to a common width, for reading in a terminal.
+    Table,
+}
+
+/// Writes the result of a query to an [`io::Write`](std::io::Write).
+///
+/// CSV and JSON Lines are written row by row as the rows are fetched. A table needs
+/// the width of every value first, so its rows are kept in memory until the end.
+/// The header comes from the columns of the query, so it is written even without rows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    format: Format,
+    null: String,
+    header: bool,
+    masked: Vec<String>,
+    mask: String,
+}
+
+impl Export {
+    /// Writes a header row, `NULL` as an empty field in CSV and as `NULL` in a table,
+    /// and masks no columns.
+    pub fn new(format: Format) -> Self {
+        Export {
+            format,
+            null: match format {
+                Format::Table => "NULL".to_string(),
+                _ => String::new(),
+            },
+            header: true,
+            masked: Vec::new(),
+            mask: "***".to_string(),
+        }
+    }
+
+    #[inline]
+    pub fn csv() -> Self {
+        Export::new(Format::Csv)
+    }
+
+    #[inline]
+    pub fn json_lines() -> Self {
+        Export::new(Format::JsonLines)
+    }
+
+    #[inline]
+    pub fn table() -> Self {
+        Export::new(Format::Table)
+    }
+
+    /// Sets the text written for `NULL`. JSON Lines always writes `null`.
+    pub fn null(mut self, text: impl Into<String>) -> Self {
+        self.null = text.into();
+        self
+    }
+
+    /// Sets whether the column names are written first. JSON Lines has no header.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Replaces every value of the columns called `column` with the mask, including `NULL`.
+    pub fn mask(mut self, column: impl Into<String>) -> Self {
+        self.masked.push(column.into());
+        self
+    }
+
+    /// Sets the text written for masked values; `***` by default.
+    pub fn mask_with(mut self, text: impl Into<String>) -> Self {
+        self.mask = text.into();
+        self
+    }
+
+    /// Runs the query and writes its rows to `out`, returning the number of rows written.
+    pub fn write<'a, W: Write>(
+        &self,
+        conn: &Connection,
+        query: impl IntoWrapString<'a>,
+        out: W,
+    ) -> Result<u64> {
+        let mut rows = conn.query_iter(query);
+        let names = rows
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        // A failed query has no columns, and its error is the first row.
+        self.write_inner(Some(names).filter(|names| !names.is_empty()), rows, out)
+    }
+
+    /// Writes rows that were already fetched, returning the number of rows written.
+    ///
+    /// The header is taken from the first row, so no rows write nothing.
+    pub fn write_rows<W: Write>(
+        &self,
+        rows: impl IntoIterator<Item = Result<Row>>,
+        out: W,
+    ) -> Result<u64> {
+        self.write_inner(None, rows, out)
+    }
+
+    /// Writes the header from `names`, or from the first row without them, then the rows.
+    fn write_inner<W: Write>(
+        &self,
+        names: Option<Vec<String>>,
+        rows: impl IntoIterator<Item = Result<Row>>,
+        mut out: W,
+    ) -> Result<u64> {
+        let mut writer = match self.format {
+            Format::Csv => Writer::Csv,
+            Format::JsonLines => Writer::JsonLines,
+            Format::Table => Writer::Table(Vec::new()),
+        };
+        if let (Some(names), true) = (&names, self.header) {
+            writer.header(&mut out, names).map_err(write_error)?;
+        }
+        let mut count = 0;
+        for row in rows {
+            let row = row?;
+            if count == 0 && names.is_none() && self.header {
+                writer
+                    .header(&mut out, &row.column_names())
+                    .map_err(write_error)?;
+            }
+            writer.row(self, &mut out, &row).map_err(write_error)?;
+            count += 1;
+        }
+        writer.finish(&mut out).map_err(write_error)?;
+        out.flush().map_err(write_error)?;
+        Ok(count)
+    }
+
+    fn field<'r>(&'r self, row: &'r Row, index: usize) -> Field<'r> {
+        if self.masked.iter().any(|column| column == row.column(index)) {
+            return Field::Masked(&self.mask);
+        }
+        // `Row::get` is `None` for `NULL`.
+        match (row.get_value(index), row.get(index)) {
+            (Some(value), Some(text)) => Field::Value(value, text),
+            _ => Field::Null,
+        }
+    }
+}
+
+fn write_error(e: io::Error) -> Error {
+    Error::Message(format!("write error: {}", e))
+}
+
+enum Field<'r> {
+    Null,
+    Masked(&'r str),
+    /// The value and its text as `Row::get` returns it.
+    Value(&'r Value<'static>, &'r str),
+}
+
+enum Writer {
+    Csv,
+    JsonLines,
+    /// The header and rows, already rendered as text, and whether each cell is a number.
+    Table(Vec<Vec<(String, bool)>>),
+}
+
+impl Writer {
+    fn header<T: AsRef<str>>(&mut self, out: &mut impl Write, names: &[T]) -> io::Result<()> {
+        match self {
+            Writer::Csv => {
+                let fields = names.iter().map(|name| csv_quote(name.as_ref(), false));
+                write_line(out, fields, ",", "\r\n")
+            }
+            Writer::JsonLines => Ok(()),
+            Writer::Table(lines) => {
+                lines.push(
+                    names
+                        .iter()
+                        .map(|name| (name.as_ref().to_string(), false))
+                        .collect(),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn row(&mut self, export: &Export, out: &mut impl Write, row: &Row) -> io::Result<()> {
+        let fields = (0..row.column_count()).map(|index| export.field(row, index));
+        match self {
+            Writer::Csv => {
+                let fields = fields.map(|field| match field {
+                    Field::Null => Cow::Borrowed(&*export.null),
+                    Field::Masked(mask) => csv_quote(mask, false),
+                    Field::Value(_, text) => csv_quote(text, true),
+                });
+                write_line(out, fields, ",", "\r\n")
+            }
+            Writer::JsonLines => {
+                let names = row.column_names();
+                let members = names.iter().zip(fields).map(|(name, field)| {
+                    let value = match field {
+                        Field::Null => Cow::Borrowed("null"),
+                        Field::Masked(mask) => Cow::Owned(json_string(mask)),
+                        Field::Value(value, text) => json_value(value, text),
+                    };
+                    format!("{}:{}", json_string(name), value)
+                });
+                out.write_all(b"{")?;
+                write_line(out, members, ",", "}\n")
+            }
+            Writer::Table(lines) => {
+                let cells = fields.map(|field| match field {
+                    Field::Null => (export.null.clone(), false),
+                    Field::Masked(mask) => (mask.to_string(), false),
+                    Field::Value(value, text) => (text.to_string(), is_number(value)),
+                });
+                lines.push(cells.collect());
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let lines = match self {
+            Writer::Table(lines) => std::mem::take(lines),
+            _ => return Ok(()),
+        };
+        let mut widths = Vec::new();
+        for line in &lines {
+            widths.resize(widths.len().max(line.len()), 0);
+            for (width, (text, _)) in widths.iter_mut().zip(line) {
+                *width = (*width).max(text.chars().count());
+            }
+        }
+        for line in &lines {
+            let cells = line.iter().zip(&widths).map(|((text, number), &width)| {
+                if *number {
+                    format!("{:>width$}", text, width = width)
+                } else {
+                    format!("{:<width$}", text, width = width)
+                }
+            });
+            let text = cells.collect::<Vec<_>>().join(" | ");
+            writeln!(out, "{}", text.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn write_line<T: AsRef<str>>(
+    out: &mut impl Write,
+    fields: impl Iterator<Item = T>,
+    separator: &str,
+    end: &str,
+) -> io::Result<()> {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            out.write_all(separator.as_bytes())?;
+        }
+        out.write_all(field.as_ref().as_bytes())?;
+    }
+    out.write_all(end.as_bytes())
+}
+
+/// Quotes a CSV field if needed. Empty text is quoted when it must differ from an empty `NULL`.
+fn csv_quote(text: &str, quote_empty: bool) -> Cow<'_, str> {
+    if text.contains([',', '"', '\r', '\n']) || (quote_empty && text.is_empty()) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn is_number(value: &Value<'_>) -> bool {
+    matches!(
+        value,
+        Value::I32(_)
+            | Value::I64(_)
+            | Value::I128(..)
+            | Value::U128(..)
+            | Value::F32(_)
+            | Value::F64(_)
+    )
+}
+
+fn json_value(value: &Value<'_>, text: &str) -> Cow<'static, str> {
+    let finite = match value {
+        Value::F32(value) => value.is_finite(),
+        Value::F64(value) => value.is_finite(),
+        _ => true,
+    };
+    if is_number(value) && finite {
+        Cow::Owned(text.to_string())
+    } else {
+        Cow::Owned(json_string(text))
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+#[cfg(feature = "sqlite")]
+mod tests {
+    use super::*;
+
+    fn export(export: Export, query: &'static str) -> String {
+        let conn = crate::sqlite::open(":memory:").unwrap();
+        conn.execute(
+            r#"CREATE TABLE users (id INTEGER, name TEXT, score REAL, password TEXT);
+               INSERT INTO users VALUES (1, 'Alice', 2.5, 'secret');
+               INSERT INTO users VALUES (10, 'Bob "B", Jr.', NULL, 'hunter2');
+               INSERT INTO users VALUES (100, '', -1.0, NULL);
+               INSERT INTO users VALUES (1000, 'line
+break', NULL, '');"#,
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        export.write(&conn, query, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            export(Export::csv(), "SELECT * FROM users"),
+            "id,name,score,password\r\n\
+             1,Alice,2.5,secret\r\n\
+             10,\"Bob \"\"B\"\", Jr.\",,hunter2\r\n\
+             100,\"\",-1.0,\r\n\
+             1000,\"line\nbreak\",,\"\"\r\n"
+        );
+        assert_eq!(
+            export(
+                Export::csv().header(false).null("\\N").mask("password"),
+                "SELECT id, score, password FROM users WHERE id < 100"
+            ),
+            "1,2.5,***\r\n10,\\N,***\r\n"
+        );
+        assert_eq!(
+            export(Export::csv(), "SELECT * FROM users WHERE id < 0"),
+            "id,name,score,password\r\n"
+        );
+        assert_eq!(
+            export(
+                Export::csv().header(false),
+                "SELECT * FROM users WHERE id < 0"
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn json_lines() {
+        assert_eq!(
+            export(Export::json_lines(), "SELECT * FROM users"),
+            "{\"id\":1,\"name\":\"Alice\",\"score\":2.5,\"password\":\"secret\"}\n\
+             {\"id\":10,\"name\":\"Bob \\\"B\\\", Jr.\",\"score\":null,\"password\":\"hunter2\"}\n\
+             {\"id\":100,\"name\":\"\",\"score\":-1.0,\"password\":null}\n\
+             {\"id\":1000,\"name\":\"line\\nbreak\",\"score\":null,\"password\":\"\"}\n"
+        );
+        assert_eq!(
+            export(
+                Export::json_lines().mask("password").mask_with("[hidden]"),
+                "SELECT id, password, X'01' AS data FROM users WHERE id = 100"
+            ),
+            "{\"id\":100,\"password\":\"[hidden]\",\"data\":\"01\"}\n"
+        );
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(
+            export(
+                Export::table(),
+                "SELECT id, name, score FROM users WHERE id < 1000"
+            ),
+            concat!(
+                "id  | name         | score\n",
+                "  1 | Alice        |   2.5\n",
+                " 10 | Bob \"B\", Jr. | NULL\n",
+                "100 |              |  -1.0\n",
+            )
+        );
+        assert_eq!(
+            export(
+                Export::table().header(false).null("-").mask("name"),
+                "SELECT id, name, score FROM users WHERE id < 100"
+            ),
+            " 1 | *** | 2.5\n10 | *** | -\n"
+        );
+        assert_eq!(
+            export(Export::table(), "SELECT id, name FROM users WHERE id < 0"),
+            "id | name\n"
+        );
+    }
+
+    #[test]
+    fn write_rows() {
+        let conn = crate::sqlite::open(":memory:").unwrap();
+        let rows = conn.rows("SELECT 1 AS a, 'x' AS b").unwrap();
+        let mut out = Vec::new();
+        let count = Export::csv()
+            .write_rows(rows.into_iter().map(Ok), &mut out)
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(out, b"a,b\r\n1,x\r\n");
+        let mut out = Vec::new();
+        assert_eq!(Export::csv().write_rows(Vec::new(), &mut out), Ok(0));
+        assert_eq!(out, b"");
+        assert!(Export::csv()
+            .write(&conn, "SELECT * FROM no_such_table", Vec::new())
+            .is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use std::cell::{RefCell, RefMut};
+use std::ptr::NonNull;
+
+use crate::error::Error;
+use crate::Result;
+
+/// A client connection that an unfinished result can take away until it is dropped.
+pub(crate) struct Handle<T> {
+    inner: RefCell<Option<Box<T>>>,
+}
+
+impl<T> Handle<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Handle {
+            inner: RefCell::new(Some(Box::new(value))),
+        }
+    }
+
+    /// Borrows the connection, unless it is in use or lent to a result.
+    pub(crate) fn borrow_mut(&self) -> Result<RefMut<'_, T>> {
+        let inner = self.inner.try_borrow_mut().map_err(|_| busy())?;
+        RefMut::filter_map(inner, |inner| inner.as_deref_mut()).map_err(|_| busy())
+    }
+
+    /// Takes the connection out of the handle until the lease is dropped.
+    pub(crate) fn lend(&self) -> Result<Lease<'_, T>> {
+        let mut inner = self.inner.try_borrow_mut().map_err(|_| busy())?;
+        let value = inner.take().ok_or_else(busy)?;
+        Ok(Lease {
+            handle: self,
+            value: NonNull::from(Box::leak(value)),
+        })
+    }
+}
+
+/// The connection of a [`Handle`], owned by a result that borrows it.
+pub(crate) struct Lease<'c, T> {
+    handle: &'c Handle<T>,
+    value: NonNull<T>,
+}
+
+impl<'c, T> Lease<'c, T> {
+    /// Returns the connection for a result to borrow.
+    ///
+    /// # Safety
+    ///
+    /// Must be called at most once, and the reference must not be used after the lease is dropped.
+    pub(crate) unsafe fn get(&mut self) -> &'c mut T {
+        &mut *self.value.as_ptr()
+    }
+}
+
+impl<T> Drop for Lease<'_, T> {
+    fn drop(&mut self) {
+        let value = unsafe { Box::from_raw(self.value.as_ptr()) };
+        *self.handle.inner.borrow_mut() = Some(value);
+    }
+}
+
+fn busy() -> Error {
+    Error::Message("connection is busy with an unfinished result".to_string())
+}
@@ -34,13 +34,25 @@
 #![allow(clippy::needless_doctest_main)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+mod cache;
+mod column;
 mod connection;
 mod error;
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+mod handle;
+mod net;
 mod parser;
+mod result_set;
 mod row;
+#[cfg(feature = "serde")]
+mod serialize;
+mod statement;
+mod transaction;
 mod value;
 mod wrapstring;
 
+pub mod export;
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
 pub mod mysql;
@@ -51,14 +63,19 @@ pub mod postgres;
 #[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
 pub mod sqlite;
 
-pub use crate::connection::{without_escape, Connection};
+pub use crate::column::Column;
+pub use crate::connection::{without_escape, Connection, QueryIter, ResultSets};
 pub use crate::error::{Error, ErrorLevel};
+pub use crate::net::{IpNetwork, MacAddr};
 pub use crate::parser::{_sanitize_like, html_special_chars, invalid_literal};
-pub use crate::row::{FromSql, Get, Row};
-pub use crate::value::{ToValue, Value};
+pub use crate::result_set::{ExecResult, ResultSet};
+pub use crate::row::{FromRow, FromSql, Get, Row};
+pub use crate::statement::Statement;
+pub use crate::transaction::{IsolationLevel, Transaction, TransactionBuilder};
+pub use crate::value::{AsBlob, Int128Repr, ToValue, Value};
 pub use crate::wrapstring::{IntoWrapString, WrapString};
 
-pub use concatsql_macro::query;
+pub use concatsql_macro::{query, FromRow, ToValue};
 
 pub mod prelude {
     //! Re-exports important traits and types.
@@ -74,11 +91,12 @@ pub mod prelude {
     pub use crate::sqlite;
 
     pub use crate::connection::{without_escape, Connection};
-    pub use crate::row::{FromSql, Get, Row};
+    pub use crate::result_set::{ExecResult, ResultSet};
+    pub use crate::row::{FromRow, FromSql, Get, Row};
     pub use crate::value::{ToValue, Value};
     pub use crate::wrapstring::WrapString;
     pub use crate::{params, sanitize_like};
-    pub use concatsql_macro::query;
+    pub use concatsql_macro::{query, FromRow, ToValue};
 }
 
 /// A typedef of the result returned by many methods.
@@ -171,7 +189,7 @@ macro_rules! prep {
 #[inline]
 #[deprecated(note = "please use `query!` instead")]
 #[allow(deprecated)]
-pub fn prep(query: &'static str) -> WrapString {
+pub fn prep(query: &'static str) -> WrapString<'static> {
     WrapString::init(query)
 }
 
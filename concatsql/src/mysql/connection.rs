@@ -1,15 +1,21 @@
 extern crate mysql_sys as mysql;
 use mysql::prelude::*;
-use mysql::{Conn, Opts};
+use mysql::{Conn, Opts, OptsBuilder, QueryResult, Statement};
 
 use std::borrow::Cow;
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, RefCell, RefMut};
+use std::convert::TryFrom;
+use std::sync::Arc;
 
-use crate::connection::{ConcatsqlConn, ConnKind, Connection};
+use crate::cache::StatementCache;
+use crate::column::{Column, Columns};
+use crate::connection::{ConcatsqlConn, ConnKind, Connection, QueryRows};
 use crate::error::{Error, ErrorLevel};
-use crate::parser::to_hex;
+use crate::handle::{Handle, Lease};
+use crate::result_set::{ExecResult, ResultSet};
 use crate::row::Row;
-use crate::value::{SystemTimeToString, Value};
+use crate::statement::param_count_error;
+use crate::value::{Int128Repr, SystemTimeToString, Value};
 use crate::Result;
 
 /// Open a read-write connection to a new or existing database.
@@ -19,14 +25,20 @@ pub fn open(url: &str) -> Result<Connection> {
         Err(e) => return Err(Error::Message(format!("failed to open: {}", e))),
     };
 
-    let conn = match Conn::new(opts) {
+    // Statements are cached by `MysqlConn` instead, which can be resized and cleared.
+    let conn = match Conn::new(OptsBuilder::from_opts(opts).stmt_cache_size(0)) {
         Ok(conn) => conn,
         Err(e) => return Err(Error::Message(format!("failed to open: {}", e))),
     };
 
     Ok(Connection {
-        conn: Box::new(RefCell::new(conn)),
+        conn: Box::new(MysqlConn {
+            conn: Handle::new(conn),
+            cache: RefCell::default(),
+            closing: RefCell::default(),
+        }),
         error_level: Cell::new(ErrorLevel::default()),
+        in_transaction: Cell::new(false),
     })
 }
 
@@ -36,38 +48,107 @@ macro_rules! to_mysql_value {
             Value::Null => mysql::Value::from(None as Option<i32>),
             Value::I32(value) => mysql::Value::from(value),
             Value::I64(value) => mysql::Value::from(value),
+            // MySQL transfers `DECIMAL` as its decimal digits.
+            Value::I128(value, Int128Repr::Text) => {
+                mysql::Value::Bytes(value.to_string().into_bytes())
+            }
+            Value::I128(value, Int128Repr::Blob) => {
+                mysql::Value::Bytes(value.to_be_bytes().to_vec())
+            }
+            Value::U128(value, Int128Repr::Text) => {
+                mysql::Value::Bytes(value.to_string().into_bytes())
+            }
+            Value::U128(value, Int128Repr::Blob) => {
+                mysql::Value::Bytes(value.to_be_bytes().to_vec())
+            }
             Value::F32(value) => mysql::Value::from(value),
             Value::F64(value) => mysql::Value::from(value),
             Value::Text(value) => mysql::Value::from(value.as_ref()),
             Value::Bytes(value) => mysql::Value::from(value),
             Value::IpAddr(value) => mysql::Value::from(value.to_string()),
+            Value::IpNetwork(value) => mysql::Value::from(value.to_string()),
+            Value::MacAddr(value) => mysql::Value::from(value.to_string()),
             Value::Time(value) => mysql::Value::from(value.to_string()),
+            Value::Interval(value) => {
+                let abs = value.abs();
+                let secs = abs.num_seconds();
+                mysql::Value::Time(
+                    *value < chrono::Duration::zero(),
+                    (secs / 86400) as u32,
+                    (secs / 3600 % 24) as u8,
+                    (secs / 60 % 60) as u8,
+                    (secs % 60) as u8,
+                    (abs.subsec_nanos() / 1000) as u32,
+                )
+            }
         }
     };
 }
 
-impl ConcatsqlConn for RefCell<mysql::Conn> {
+/// A MySQL connection and the statements it keeps prepared.
+pub(crate) struct MysqlConn {
+    conn: Handle<Conn>,
+    cache: RefCell<StatementCache<Statement>>,
+    /// Statements displaced from the cache, closed once no result is being read.
+    closing: RefCell<Vec<Statement>>,
+}
+
+impl MysqlConn {
+    fn borrow_mut(&self) -> Result<RefMut<'_, Conn>> {
+        let mut conn = self.conn.borrow_mut()?;
+        self.close_displaced(&mut conn);
+        Ok(conn)
+    }
+
+    fn close_displaced(&self, conn: &mut Conn) {
+        for statement in self.closing.borrow_mut().drain(..) {
+            let _ = conn.close(statement);
+        }
+    }
+
+    /// Returns the cached statement for `query`, or prepares it.
+    fn prepare(&self, conn: &mut Conn, query: &str) -> mysql::Result<Statement> {
+        if let Some(statement) = self.cache.borrow_mut().get(query) {
+            return Ok(statement.clone());
+        }
+        let statement = conn.prep(query)?;
+        // Without a cache, this is `statement` itself, closed after it is used.
+        let displaced = self
+            .cache
+            .borrow_mut()
+            .insert(query.to_string(), statement.clone());
+        self.closing.borrow_mut().extend(displaced);
+        Ok(statement)
+    }
+}
+
+impl ConcatsqlConn for MysqlConn {
     fn execute_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-    ) -> Result<()> {
-        let mut conn = self.borrow_mut();
-        if params.is_empty() {
-            match conn.query_drop(&query) {
-                Ok(_) => Ok(()),
-                Err(e) => Error::new(error_level, "exec error", &e),
-            }
+        prepared: bool,
+    ) -> Result<ExecResult> {
+        let mut conn = self.borrow_mut()?;
+        let result = if params.is_empty() && !prepared {
+            conn.query_drop(&query)
         } else {
             let params = params
                 .iter()
                 .map(|value| to_mysql_value!(value))
                 .collect::<Vec<_>>();
-            match conn.exec_drop(&query, params) {
-                Ok(_) => Ok(()),
-                Err(e) => Error::new(error_level, "exec error", &e),
-            }
+            self.prepare(&mut conn, &query)
+                .and_then(|statement| conn.exec_drop(&statement, params))
+        };
+        match result {
+            Ok(_) => Ok(ExecResult::new(
+                conn.affected_rows(),
+                i64::try_from(conn.last_insert_id())
+                    .ok()
+                    .filter(|&id| id != 0),
+            )),
+            Err(e) => Error::new(error_level, "exec error", &e).map(|_| ExecResult::default()),
         }
     }
 
@@ -76,7 +157,7 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-        callback: &mut dyn FnMut(&[(&str, Option<&str>)]) -> bool,
+        callback: &mut dyn FnMut(&Row) -> bool,
     ) -> Result<()> {
         macro_rules! run {
             ($result:expr) => {
@@ -85,31 +166,27 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
                         Ok(result_set) => result_set,
                         Err(e) => return Error::new(error_level, "exec error", &e),
                     };
-                    let mut pairs: Vec<(String, Option<String>)> =
-                        Vec::with_capacity(result_set.affected_rows() as usize);
+                    let columns = columns(result_set.columns().as_ref());
 
                     for row in result_set {
                         let row = match row {
                             Ok(row) => row,
                             Err(e) => return Error::new(error_level, "exec error", &e),
                         };
-
-                        for (index, col) in row.columns().iter().enumerate() {
-                            let value = row.get_to_string(index);
-                            pairs.push((col.name_str().to_string(), value));
+                        let row = Row::from_values(
+                            columns.clone(),
+                            (0..row.len()).map(|index| row.get_value(index)),
+                        );
+                        // Dropping the result reads and discards the remaining rows.
+                        if !callback(&row) {
+                            return Ok(());
                         }
                     }
-
-                    let pairs: Vec<(&str, Option<&str>)> =
-                        pairs.iter().map(|p| (&*p.0, p.1.as_deref())).collect();
-                    if !pairs.is_empty() && !callback(&pairs) {
-                        return Error::new(error_level, "exec error", "query aborted");
-                    }
                 }
             };
         }
 
-        let mut conn = self.borrow_mut();
+        let mut conn = self.borrow_mut()?;
 
         if params.is_empty() {
             let mut result = match conn.query_iter(&query) {
@@ -122,7 +199,11 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
                 .iter()
                 .map(|value| to_mysql_value!(value))
                 .collect::<Vec<_>>();
-            let mut result = match conn.exec_iter(&query, params) {
+            let statement = match self.prepare(&mut conn, &query) {
+                Ok(statement) => statement,
+                Err(e) => return Error::new(error_level, "exec error", &e),
+            };
+            let mut result = match conn.exec_iter(&statement, params) {
                 Ok(result) => result,
                 Err(e) => return Error::new(error_level, "exec error", &e),
             };
@@ -132,13 +213,14 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
         Ok(())
     }
 
-    fn rows_inner<'a, 'r>(
+    fn rows_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-    ) -> Result<Vec<Row<'r>>> {
-        let mut conn = self.borrow_mut();
+        prepared: bool,
+    ) -> Result<Vec<Row>> {
+        let mut conn = self.borrow_mut()?;
 
         macro_rules! run {
             ($result:expr, $rows:expr) => {
@@ -161,37 +243,14 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
                             }
                         };
 
-                        let column_len = result_row.columns_ref().len();
-
-                        if first_row {
+                        let columns = if first_row {
                             first_row = false;
-                            let columns = result_row
-                                .columns_ref()
-                                .iter()
-                                .map(|col| col.name_str().to_string())
-                                .collect();
-                            let mut row = Row::new(columns);
-                            for index in 0..column_len {
-                                unsafe {
-                                    row.insert(
-                                        &*(row.column(index) as *const str),
-                                        result_row.get_to_string(index),
-                                    );
-                                }
-                            }
-                            $rows.push(row);
+                            columns(result_row.columns_ref())
                         } else {
-                            let mut row = Row::new($rows[0].columns());
-                            for index in 0..column_len {
-                                unsafe {
-                                    row.insert(
-                                        &*($rows[0].column(index) as *const str),
-                                        result_row.get_to_string(index),
-                                    );
-                                }
-                            }
-                            $rows.push(row);
-                        }
+                            $rows[0].shared_columns()
+                        };
+                        let values = (0..result_row.len()).map(|index| result_row.get_value(index));
+                        $rows.push(Row::from_values(columns, values));
                     }
                 }
             };
@@ -199,7 +258,7 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
 
         let mut rows: Vec<Row> = Vec::new();
 
-        if params.is_empty() {
+        if params.is_empty() && !prepared {
             let mut result = match conn.query_iter(&query) {
                 Ok(result) => result,
                 Err(e) => return Error::new(error_level, "exec error", &e).map(|_| Vec::new()),
@@ -210,7 +269,11 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
                 .iter()
                 .map(|value| to_mysql_value!(value))
                 .collect::<Vec<_>>();
-            let mut result = match conn.exec_iter(&query, params) {
+            let statement = match self.prepare(&mut conn, &query) {
+                Ok(statement) => statement,
+                Err(e) => return Error::new(error_level, "exec error", &e).map(|_| Vec::new()),
+            };
+            let mut result = match conn.exec_iter(&statement, params) {
                 Ok(result) => result,
                 Err(e) => return Error::new(error_level, "exec error", &e).map(|_| Vec::new()),
             };
@@ -220,6 +283,138 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
         Ok(rows)
     }
 
+    fn query_iter_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &ErrorLevel,
+        prepared: bool,
+    ) -> Result<Box<dyn QueryRows + 'c>> {
+        let mut lease = self.conn.lend()?;
+        // The result is dropped before the lease, see `MysqlRows`.
+        let conn = unsafe { lease.get() };
+        self.close_displaced(conn);
+
+        if params.is_empty() && !prepared {
+            match conn.query_iter(&query) {
+                Ok(result) => Ok(Box::new(MysqlRows::new(result, lease, error_level))),
+                Err(e) => Error::new(error_level, "exec error", &e)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>),
+            }
+        } else {
+            let params = params
+                .iter()
+                .map(|value| to_mysql_value!(value))
+                .collect::<Vec<_>>();
+            let statement = match self.prepare(conn, &query) {
+                Ok(statement) => statement,
+                Err(e) => {
+                    return Error::new(error_level, "exec error", &e)
+                        .map(|_| Box::new(std::iter::empty()) as Box<_>)
+                }
+            };
+            match conn.exec_iter(&statement, params) {
+                Ok(result) => Ok(Box::new(MysqlRows::new(result, lease, error_level))),
+                Err(e) => Error::new(error_level, "exec error", &e)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>),
+            }
+        }
+    }
+
+    fn result_sets_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &ErrorLevel,
+    ) -> Result<Box<dyn Iterator<Item = Result<ResultSet>> + 'c>> {
+        let mut lease = self.conn.lend()?;
+        // The result is dropped before the lease, see `MysqlResultSets`.
+        let conn = unsafe { lease.get() };
+        self.close_displaced(conn);
+
+        if params.is_empty() {
+            match conn.query_iter(&query) {
+                Ok(result) => Ok(Box::new(MysqlResultSets::new(result, lease, error_level))),
+                Err(e) => Error::new(error_level, "exec error", &e)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>),
+            }
+        } else {
+            let params = params
+                .iter()
+                .map(|value| to_mysql_value!(value))
+                .collect::<Vec<_>>();
+            let statement = match self.prepare(conn, &query) {
+                Ok(statement) => statement,
+                Err(e) => {
+                    return Error::new(error_level, "exec error", &e)
+                        .map(|_| Box::new(std::iter::empty()) as Box<_>)
+                }
+            };
+            match conn.exec_iter(&statement, params) {
+                Ok(result) => Ok(Box::new(MysqlResultSets::new(result, lease, error_level))),
+                Err(e) => Error::new(error_level, "exec error", &e)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>),
+            }
+        }
+    }
+
+    fn execute_many_inner<'a>(
+        &self,
+        query: &str,
+        params: &mut dyn Iterator<Item = Vec<Value<'a>>>,
+        error_level: &ErrorLevel,
+    ) -> Result<u64> {
+        let mut conn = self.borrow_mut()?;
+        let statement = match self.prepare(&mut conn, query) {
+            Ok(statement) => statement,
+            Err(e) => return Error::new(error_level, "exec error", &e).map(|_| 0),
+        };
+        let count = usize::from(statement.num_params());
+
+        // What `exec_batch` does, but adding up the affected rows of each set.
+        let mut total = 0;
+        for row in params {
+            if row.len() != count {
+                return param_count_error(error_level, count, row.len()).map(|_| total);
+            }
+            let row = row
+                .iter()
+                .map(|value| to_mysql_value!(value))
+                .collect::<Vec<_>>();
+            match conn.exec_drop(&statement, row) {
+                Ok(()) => total += conn.affected_rows(),
+                Err(e) => return Error::new(error_level, "exec error", &e).map(|_| total),
+            }
+        }
+        Ok(total)
+    }
+
+    fn prepare_inner(&self, query: &str, error_level: &ErrorLevel) -> Result<usize> {
+        match self.prepare(&mut *self.borrow_mut()?, query) {
+            Ok(statement) => Ok(usize::from(statement.num_params())),
+            Err(e) => Error::new(error_level, "exec error", &e).map(|_| 0),
+        }
+    }
+
+    fn pin_statement(&self, query: &str) {
+        self.cache.borrow_mut().pin(query);
+    }
+
+    fn unpin_statement(&self, query: &str) {
+        let evicted = self.cache.borrow_mut().unpin(query);
+        self.closing.borrow_mut().extend(evicted);
+    }
+
+    fn set_statement_cache_capacity(&self, capacity: usize) {
+        let evicted = self.cache.borrow_mut().set_capacity(capacity);
+        self.closing.borrow_mut().extend(evicted);
+    }
+
+    fn clear_statement_cache(&self) {
+        let evicted = self.cache.borrow_mut().clear();
+        self.closing.borrow_mut().extend(evicted);
+    }
+
     fn close(&self) {
         // Do nothing
     }
@@ -230,46 +425,270 @@ impl ConcatsqlConn for RefCell<mysql::Conn> {
     }
 }
 
-trait GetToString {
-    fn get_to_string(&self, index: usize) -> Option<String>;
+/// Reads the first result set of a query one row at a time.
+///
+/// The connection is taken away until the rows run out or the iterator is dropped.
+struct MysqlRows<'c, T: Protocol> {
+    // The result comes first so that it is dropped before the lease.
+    inner: Option<(QueryResult<'c, 'c, 'c, T>, Lease<'c, Conn>)>,
+    columns: Arc<Columns>,
+    error_level: ErrorLevel,
 }
-impl GetToString for mysql::Row {
-    fn get_to_string(&self, index: usize) -> Option<String> {
+
+impl<'c, T: Protocol> MysqlRows<'c, T> {
+    fn new(
+        result: QueryResult<'c, 'c, 'c, T>,
+        lease: Lease<'c, Conn>,
+        error_level: &ErrorLevel,
+    ) -> Self {
+        MysqlRows {
+            columns: columns(result.columns().as_ref()),
+            inner: Some((result, lease)),
+            error_level: *error_level,
+        }
+    }
+}
+
+impl<T: Protocol> Iterator for MysqlRows<'_, T> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (result, _) = self.inner.as_mut()?;
+        match result.next() {
+            Some(Ok(row)) => Some(Ok(Row::from_values(
+                self.columns.clone(),
+                (0..row.len()).map(|index| row.get_value(index)),
+            ))),
+            Some(Err(e)) => {
+                self.inner = None;
+                Error::new(&self.error_level, "exec error", &e)
+                    .err()
+                    .map(Err)
+            }
+            None => {
+                self.inner = None;
+                None
+            }
+        }
+    }
+}
+
+impl<T: Protocol> QueryRows for MysqlRows<'_, T> {
+    fn columns(&mut self) -> &[Column] {
+        &self.columns
+    }
+}
+
+trait GetValue {
+    fn get_value(&self, index: usize) -> Value<'static>;
+}
+impl GetValue for mysql::Row {
+    fn get_value(&self, index: usize) -> Value<'static> {
         match self[index] {
-            mysql::Value::NULL => None,
-            mysql::Value::Int(v) => Some(v.to_string()),
-            mysql::Value::UInt(v) => Some(v.to_string()), // unreachable ?
-            mysql::Value::Float(v) => Some(v.to_string()), // unreachable ?
-            mysql::Value::Double(v) => Some(v.to_string()), // unreachable ?
-            mysql::Value::Bytes(ref bytes) => match String::from_utf8(bytes.to_vec()) {
-                Ok(string) => Some(string),
-                Err(_) => Some(to_hex(bytes)),
+            mysql::Value::NULL => Value::Null,
+            mysql::Value::Int(v) => Value::I64(v),
+            mysql::Value::UInt(v) => match i64::try_from(v) {
+                Ok(v) => Value::I64(v),
+                Err(_) => Value::U128(u128::from(v), Int128Repr::Text),
             },
-            mysql::Value::Date(year, month, day, hour, minute, second, micros) => Some(format!(
-                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
-                year, month, day, hour, minute, second, micros
-            )), // unreachable ?
-            mysql::Value::Time(neg, days, hours, minutes, seconds, micros) => Some(if neg {
-                format!(
-                    "-{:03}:{:02}:{:02}.{:06}",
+            mysql::Value::Float(v) => Value::F32(v),
+            mysql::Value::Double(v) => Value::F64(v),
+            mysql::Value::Bytes(ref bytes) => text_value(&self.columns_ref()[index], bytes),
+            mysql::Value::Date(year, month, day, hour, minute, second, micros) => {
+                Value::Text(Cow::Owned(format!(
+                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+                    year, month, day, hour, minute, second, micros
+                )))
+            } // unreachable ?
+            mysql::Value::Time(neg, days, hours, minutes, seconds, micros) => {
+                Value::Text(Cow::Owned(format!(
+                    "{}{:03}:{:02}:{:02}.{:06}",
+                    if neg { "-" } else { "" },
                     days * 24 + u32::from(hours),
                     minutes,
                     seconds,
                     micros
-                )
-            } else {
-                format!(
-                    "{:03}:{:02}:{:02}.{:06}",
-                    days * 24 + u32::from(hours),
-                    minutes,
-                    seconds,
-                    micros
-                )
-            }), // unreachable ?
+                )))
+            } // unreachable ?
+        }
+    }
+}
+
+/// Reads every result set of a query, one set at a time.
+///
+/// The connection is taken away until the sets run out or the iterator is dropped.
+struct MysqlResultSets<'c, T: Protocol> {
+    // The result comes first so that it is dropped before the lease.
+    inner: Option<(QueryResult<'c, 'c, 'c, T>, Lease<'c, Conn>)>,
+    error_level: ErrorLevel,
+}
+
+impl<'c, T: Protocol> MysqlResultSets<'c, T> {
+    fn new(
+        result: QueryResult<'c, 'c, 'c, T>,
+        lease: Lease<'c, Conn>,
+        error_level: &ErrorLevel,
+    ) -> Self {
+        MysqlResultSets {
+            inner: Some((result, lease)),
+            error_level: *error_level,
+        }
+    }
+
+    fn next_set(
+        result: &mut QueryResult<'c, 'c, 'c, T>,
+    ) -> std::result::Result<Option<ResultSet>, mysql::Error> {
+        let mut result_set = match result.next_set() {
+            Some(result_set) => result_set?,
+            None => return Ok(None),
+        };
+        let columns = columns(result_set.columns().as_ref());
+        let mut rows = Vec::new();
+        for row in result_set.by_ref() {
+            let row = row?;
+            rows.push(Row::from_values(
+                columns.clone(),
+                (0..row.len()).map(|index| row.get_value(index)),
+            ));
+        }
+        let rows_affected = result_set.affected_rows();
+        Ok(Some(ResultSet::new(columns, rows, rows_affected)))
+    }
+}
+
+impl<T: Protocol> Iterator for MysqlResultSets<'_, T> {
+    type Item = Result<ResultSet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (result, _) = self.inner.as_mut()?;
+        match Self::next_set(result) {
+            Ok(Some(result_set)) => Some(Ok(result_set)),
+            Ok(None) => {
+                self.inner = None;
+                None
+            }
+            Err(e) => {
+                self.inner = None;
+                Error::new(&self.error_level, "exec error", &e)
+                    .err()
+                    .map(Err)
+            }
         }
     }
 }
 
+fn columns(columns: &[mysql::Column]) -> Arc<Columns> {
+    use mysql::consts::{ColumnFlags, ColumnType::*};
+
+    let columns = columns
+        .iter()
+        .map(|col| {
+            let flags = col.flags();
+            let mut column = Column::new(col.name_str());
+            column.decl_type = Some(type_name(col).to_string());
+            column.nullable = Some(!flags.contains(ColumnFlags::NOT_NULL_FLAG));
+            column.table = Some(col.org_table_str().into_owned()).filter(|s| !s.is_empty());
+            column.origin_name = Some(col.org_name_str().into_owned()).filter(|s| !s.is_empty());
+            if matches!(
+                col.column_type(),
+                MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL
+            ) {
+                // The display length counts the sign and the decimal point.
+                let scale = u32::from(col.decimals());
+                let sign = u32::from(!flags.contains(ColumnFlags::UNSIGNED_FLAG));
+                let point = u32::from(scale > 0);
+                column.precision = col.column_length().checked_sub(sign + point);
+                column.scale = Some(scale);
+            }
+            column
+        })
+        .collect();
+    Arc::new(columns)
+}
+
+/// The SQL name of the column type, as far as the protocol tells it apart.
+fn type_name(column: &mysql::Column) -> &'static str {
+    use mysql::consts::{ColumnFlags, ColumnType::*};
+
+    let binary = column.character_set() == BINARY_CHARSET;
+    match column.column_type() {
+        MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => "DECIMAL",
+        MYSQL_TYPE_TINY => "TINYINT",
+        MYSQL_TYPE_SHORT => "SMALLINT",
+        MYSQL_TYPE_INT24 => "MEDIUMINT",
+        MYSQL_TYPE_LONG => "INT",
+        MYSQL_TYPE_LONGLONG => "BIGINT",
+        MYSQL_TYPE_FLOAT => "FLOAT",
+        MYSQL_TYPE_DOUBLE => "DOUBLE",
+        MYSQL_TYPE_NULL => "NULL",
+        MYSQL_TYPE_TIMESTAMP | MYSQL_TYPE_TIMESTAMP2 => "TIMESTAMP",
+        MYSQL_TYPE_DATE | MYSQL_TYPE_NEWDATE => "DATE",
+        MYSQL_TYPE_TIME | MYSQL_TYPE_TIME2 => "TIME",
+        MYSQL_TYPE_DATETIME | MYSQL_TYPE_DATETIME2 => "DATETIME",
+        MYSQL_TYPE_YEAR => "YEAR",
+        MYSQL_TYPE_BIT => "BIT",
+        MYSQL_TYPE_JSON => "JSON",
+        MYSQL_TYPE_ENUM => "ENUM",
+        MYSQL_TYPE_SET => "SET",
+        MYSQL_TYPE_GEOMETRY => "GEOMETRY",
+        MYSQL_TYPE_STRING if column.flags().contains(ColumnFlags::ENUM_FLAG) => "ENUM",
+        MYSQL_TYPE_STRING if column.flags().contains(ColumnFlags::SET_FLAG) => "SET",
+        MYSQL_TYPE_STRING if binary => "BINARY",
+        MYSQL_TYPE_STRING => "CHAR",
+        MYSQL_TYPE_VARCHAR | MYSQL_TYPE_VAR_STRING if binary => "VARBINARY",
+        MYSQL_TYPE_VARCHAR | MYSQL_TYPE_VAR_STRING => "VARCHAR",
+        MYSQL_TYPE_TINY_BLOB | MYSQL_TYPE_MEDIUM_BLOB | MYSQL_TYPE_LONG_BLOB | MYSQL_TYPE_BLOB
+            if binary =>
+        {
+            "BLOB"
+        }
+        MYSQL_TYPE_TINY_BLOB | MYSQL_TYPE_MEDIUM_BLOB | MYSQL_TYPE_LONG_BLOB | MYSQL_TYPE_BLOB => {
+            "TEXT"
+        }
+    }
+}
+
+/// The `binary` character set of `BLOB`, `BINARY` and `VARBINARY` columns.
+const BINARY_CHARSET: u16 = 63;
+
+/// The text protocol sends every value as a string; numbers are recovered from the column type.
+/// Binary strings are kept as bytes even if they happen to be valid UTF-8.
+fn text_value(column: &mysql::Column, bytes: &[u8]) -> Value<'static> {
+    use mysql::consts::ColumnType::*;
+
+    let column_type = column.column_type();
+    if column.character_set() == BINARY_CHARSET
+        && matches!(
+            column_type,
+            MYSQL_TYPE_TINY_BLOB
+                | MYSQL_TYPE_MEDIUM_BLOB
+                | MYSQL_TYPE_LONG_BLOB
+                | MYSQL_TYPE_BLOB
+                | MYSQL_TYPE_STRING
+                | MYSQL_TYPE_VAR_STRING
+                | MYSQL_TYPE_VARCHAR
+        )
+    {
+        return Value::Bytes(bytes.to_vec());
+    }
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(_) => return Value::Bytes(bytes.to_vec()),
+    };
+    let value = match column_type {
+        MYSQL_TYPE_TINY | MYSQL_TYPE_SHORT | MYSQL_TYPE_INT24 | MYSQL_TYPE_LONG
+        | MYSQL_TYPE_LONGLONG | MYSQL_TYPE_YEAR => text
+            .parse()
+            .map(Value::I64)
+            .or_else(|_| text.parse().map(|v| Value::U128(v, Int128Repr::Text)))
+            .ok(),
+        MYSQL_TYPE_FLOAT => text.parse().map(Value::F32).ok(),
+        MYSQL_TYPE_DOUBLE => text.parse().map(Value::F64).ok(),
+        _ => None,
+    };
+    value.unwrap_or_else(|| Value::Text(Cow::Owned(text.to_owned())))
+}
+
 #[cfg(test)]
 mod tests {
     use crate as concatsql;
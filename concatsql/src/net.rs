@@ -0,0 +1,163 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// An IP address with a network prefix length, such as PostgreSQL `inet` or `cidr`.
+///
+/// ```
+/// use concatsql::IpNetwork;
+///
+/// let net: IpNetwork = "192.168.0.0/24".parse().unwrap();
+/// assert_eq!(net.prefix(), 24);
+/// assert_eq!(net.to_string(), "192.168.0.0/24");
+/// assert_eq!("::1".parse::<IpNetwork>().unwrap().prefix(), 128);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Returns `None` if `prefix` is longer than the address.
+    pub fn new(addr: IpAddr, prefix: u8) -> Option<Self> {
+        if prefix <= max_prefix(&addr) {
+            Some(IpNetwork { addr, prefix })
+        } else {
+            None
+        }
+    }
+
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+}
+
+pub(crate) fn max_prefix(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+impl From<IpAddr> for IpNetwork {
+    fn from(addr: IpAddr) -> Self {
+        IpNetwork {
+            addr,
+            prefix: max_prefix(&addr),
+        }
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// Parses `addr/prefix`, or a bare address as a single host.
+impl FromStr for IpNetwork {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.split_once('/') {
+            Some((addr, prefix)) => {
+                let addr = addr.parse().map_err(|_| Error::ParseError)?;
+                let prefix = prefix.parse().map_err(|_| Error::ParseError)?;
+                IpNetwork::new(addr, prefix).ok_or(Error::ParseError)
+            }
+            None => Ok(IpNetwork::from(
+                s.parse::<IpAddr>().map_err(|_| Error::ParseError)?,
+            )),
+        }
+    }
+}
+
+/// A 6-byte MAC address, such as PostgreSQL `macaddr`.
+///
+/// ```
+/// use concatsql::MacAddr;
+///
+/// let mac: MacAddr = "08-00-2B-01-02-03".parse().unwrap();
+/// assert_eq!(mac, MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]));
+/// assert_eq!(mac.to_string(), "08:00:2b:01:02:03");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            a, b, c, d, e, g
+        )
+    }
+}
+
+/// Parses six hex octets separated by `:` or `-`.
+impl FromStr for MacAddr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut octets = [0u8; 6];
+        let mut parts = s.split([':', '-']);
+        for octet in octets.iter_mut() {
+            let part = parts.next().ok_or(Error::ParseError)?;
+            if part.len() != 2 {
+                return Err(Error::ParseError);
+            }
+            *octet = u8::from_str_radix(part, 16).map_err(|_| Error::ParseError)?;
+        }
+        if parts.next().is_some() {
+            return Err(Error::ParseError);
+        }
+        Ok(MacAddr(octets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn ip_network() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0));
+        assert_eq!("10.0.0.0/8".parse(), Ok(IpNetwork::new(v4, 8).unwrap()));
+        assert_eq!("10.0.0.0".parse(), Ok(IpNetwork::new(v4, 32).unwrap()));
+        assert_eq!(IpNetwork::new(v4, 33), None);
+        assert_eq!("10.0.0.0/33".parse::<IpNetwork>(), Err(Error::ParseError));
+        assert_eq!("10.0.0.0/".parse::<IpNetwork>(), Err(Error::ParseError));
+        assert_eq!("10.0.0/8".parse::<IpNetwork>(), Err(Error::ParseError));
+
+        let v6 = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+        let net = IpNetwork::new(v6, 32).unwrap();
+        assert_eq!(net.to_string(), "2001:db8::/32");
+        assert_eq!("2001:db8::/32".parse(), Ok(net));
+    }
+
+    #[test]
+    fn mac_addr() {
+        let mac = MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]);
+        assert_eq!("08:00:2b:01:02:03".parse(), Ok(mac));
+        assert_eq!("08-00-2B-01-02-03".parse(), Ok(mac));
+        assert_eq!(mac.to_string(), "08:00:2b:01:02:03");
+        assert_eq!("08:00:2b:01:02".parse::<MacAddr>(), Err(Error::ParseError));
+        assert_eq!(
+            "08:00:2b:01:02:03:04".parse::<MacAddr>(),
+            Err(Error::ParseError)
+        );
+        assert_eq!(
+            "8:00:2b:01:02:03".parse::<MacAddr>(),
+            Err(Error::ParseError)
+        );
+    }
+}
@@ -113,6 +113,83 @@ pub(crate) fn to_binary_literal(bytes: &[u8]) -> String {
     }
 }
 
+/// Parses an interval.
+///
+/// Accepted formats are `[-]hh:mm:ss[.fffffffff]` (the MySQL `TIME` format, hours may exceed 24),
+/// the PostgreSQL interval output such as `1 year 2 mons -3 days +04:05:06.5`,
+/// and a plain number of seconds.
+/// A month is counted as 30 days and a year as 12 months, as PostgreSQL's `justify_interval` does.
+pub(crate) fn parse_interval(s: &str) -> Option<chrono::Duration> {
+    use chrono::Duration;
+
+    let s = s.trim();
+    if let Ok(secs) = s.parse::<f64>() {
+        let interval =
+            Duration::from_std(std::time::Duration::try_from_secs_f64(secs.abs()).ok()?).ok()?;
+        return Some(if secs < 0.0 { -interval } else { interval });
+    }
+
+    let mut interval = Duration::zero();
+    let mut tokens = s.split_whitespace().peekable();
+    tokens.peek()?;
+    while let Some(token) = tokens.next() {
+        let part = if token.contains(':') {
+            parse_clock(token)?
+        } else {
+            let n: i64 = token.parse().ok()?;
+            let unit_secs = match tokens.next()? {
+                "year" | "years" => 31_104_000,
+                "mon" | "mons" => 2_592_000,
+                "day" | "days" => 86_400,
+                _ => return None,
+            };
+            Duration::try_seconds(n.checked_mul(unit_secs)?)?
+        };
+        interval = interval.checked_add(&part)?;
+    }
+    Some(interval)
+}
+
+/// Parses `[+-]h+:mm[:ss[.f+]]`.
+fn parse_clock(s: &str) -> Option<chrono::Duration> {
+    use chrono::Duration;
+
+    let (negative, s) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let mut parts = s.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let (seconds, nanos) = match parts.next() {
+        Some(seconds) => match seconds.split_once('.') {
+            Some((seconds, fraction)) => {
+                if fraction.is_empty()
+                    || fraction.len() > 9
+                    || !fraction.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return None;
+                }
+                let nanos: u32 = format!("{:0<9}", fraction).parse().ok()?;
+                (seconds.parse::<i64>().ok()?, nanos)
+            }
+            None => (seconds.parse::<i64>().ok()?, 0),
+        },
+        None => (0, 0),
+    };
+    if parts.next().is_some()
+        || hours < 0
+        || !(0..60).contains(&minutes)
+        || !(0..60).contains(&seconds)
+    {
+        return None;
+    }
+    let secs = hours.checked_mul(3600)? + minutes * 60 + seconds;
+    let interval = Duration::new(secs, nanos)?;
+    Some(if negative { -interval } else { interval })
+}
+
 #[doc(hidden)]
 pub fn invalid_literal() -> &'static str {
     #[cfg(debug_assertions)]
@@ -132,6 +209,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_interval() {
+        use super::parse_interval;
+        use chrono::Duration;
+
+        let hms = |h: i64, m: i64, s: i64| Duration::seconds(h * 3600 + m * 60 + s);
+        assert_eq!(parse_interval("01:02:03"), Some(hms(1, 2, 3)));
+        assert_eq!(parse_interval("123:00:00"), Some(hms(123, 0, 0)));
+        assert_eq!(
+            parse_interval("-838:59:59.500000"),
+            Some(-(hms(838, 59, 59) + Duration::milliseconds(500)))
+        );
+        assert_eq!(
+            parse_interval("00:00:00.000001"),
+            Some(Duration::microseconds(1))
+        );
+        assert_eq!(parse_interval("1 day"), Some(hms(24, 0, 0)));
+        assert_eq!(parse_interval("2 days 01:00:00"), Some(hms(49, 0, 0)));
+        assert_eq!(parse_interval("-1 days +02:00:00"), Some(-hms(22, 0, 0)));
+        assert_eq!(parse_interval("1 mon"), Some(Duration::days(30)));
+        assert_eq!(parse_interval("1 year 2 mons"), Some(Duration::days(420)));
+        assert_eq!(parse_interval("90"), Some(Duration::seconds(90)));
+        assert_eq!(parse_interval("-1.5"), Some(-Duration::milliseconds(1500)));
+        assert_eq!(parse_interval(""), None);
+        assert_eq!(parse_interval("1 week"), None);
+        assert_eq!(parse_interval("01:60:00"), None);
+        assert_eq!(parse_interval("01:00:00."), None);
+        assert_eq!(parse_interval("01:00:00:00"), None);
+        assert_eq!(parse_interval("abc"), None);
+    }
+
     #[test]
     #[cfg(feature = "sqlite")]
     #[cfg(not(all(feature = "sqlite", feature = "mysql", feature = "postgres")))]
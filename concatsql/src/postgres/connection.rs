@@ -1,16 +1,27 @@
 extern crate postgres_sys as postgres;
 
-use postgres::{Client, NoTls};
+use postgres::fallible_iterator::FallibleIterator;
+use postgres::types::{ToSql, Type};
+use postgres::{Client, NoTls, RowIter, SimpleQueryMessage, Statement};
 use uuid::Uuid;
 
 use std::borrow::Cow;
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::hash_map::{Entry, HashMap};
+use std::sync::Arc;
 use std::time::SystemTime;
 
-use crate::connection::{ConcatsqlConn, ConnKind, Connection};
+use crate::cache::StatementCache;
+use crate::column::{Column, Columns};
+use crate::connection::{ConcatsqlConn, ConnKind, Connection, QueryRows};
 use crate::error::{Error, ErrorLevel};
+use crate::handle::{Handle, Lease};
+use crate::net::MacAddr;
+use crate::postgres::types::{Interval, Network, NumericText};
+use crate::result_set::{ExecResult, ResultSet};
 use crate::row::Row;
-use crate::value::{SystemTimeToString, Value};
+use crate::statement::param_count_error;
+use crate::value::Value;
 use crate::Result;
 
 /// Open a read-write connection to a new or existing database.
@@ -21,48 +32,116 @@ pub fn open(params: &str) -> Result<Connection> {
     };
 
     Ok(Connection {
-        conn: Box::new(RefCell::new(conn)),
+        conn: Box::new(PostgresConn {
+            client: Handle::new(conn),
+            cache: RefCell::default(),
+            tables: RefCell::default(),
+        }),
         error_level: Cell::new(ErrorLevel::default()),
+        in_transaction: Cell::new(false),
     })
 }
 
-macro_rules! to_sql {
-    ($value:expr) => {
-        match $value {
-            Value::Null => &"NULL" as &(dyn postgres::types::ToSql + Sync),
-            Value::I32(value) => value,
-            Value::I64(value) => value,
-            Value::F32(value) => value,
-            Value::F64(value) => value,
-            Value::Text(value) => value,
-            Value::Bytes(value) => value,
-            Value::IpAddr(value) => value,
-            Value::Time(value) => value,
+/// A PostgreSQL client and the statements it keeps prepared.
+pub(crate) struct PostgresConn {
+    client: Handle<Client>,
+    cache: RefCell<StatementCache<Statement>>,
+    /// The tables seen in results so far, by OID.
+    tables: RefCell<HashMap<u32, Table>>,
+}
+
+/// The name of a table and of its columns by number.
+struct Table {
+    name: String,
+    columns: HashMap<i16, String>,
+}
+
+impl PostgresConn {
+    #[inline]
+    fn borrow_mut(&self) -> Result<RefMut<'_, Client>> {
+        self.client.borrow_mut()
+    }
+
+    /// Returns the cached statement for `query`, or prepares it.
+    ///
+    /// A statement is closed on the server once it is evicted and no longer in use.
+    fn prepare(
+        &self,
+        client: &mut Client,
+        query: &str,
+    ) -> std::result::Result<Statement, postgres::Error> {
+        if let Some(statement) = self.cache.borrow_mut().get(query) {
+            return Ok(statement.clone());
         }
-    };
+        let statement = client.prepare(query)?;
+        self.cache
+            .borrow_mut()
+            .insert(query.to_string(), statement.clone());
+        Ok(statement)
+    }
+
+    /// The metadata of the result columns, with the table and column each one is read from.
+    ///
+    /// Results only carry table OIDs, so each new table is looked up in the catalog once.
+    fn columns(&self, client: &mut Client, columns: &[postgres::Column]) -> Arc<Columns> {
+        let mut tables = self.tables.borrow_mut();
+        for oid in columns.iter().filter_map(|col| col.table_oid()) {
+            if let Entry::Vacant(entry) = tables.entry(oid) {
+                if let Some(table) = table(client, oid) {
+                    entry.insert(table);
+                }
+            }
+        }
+        let columns = columns
+            .iter()
+            .map(|col| {
+                let mut column = column(col);
+                if let (Some(table), Some(id)) = (
+                    col.table_oid().and_then(|oid| tables.get(&oid)),
+                    col.column_id(),
+                ) {
+                    column.table = Some(table.name.clone());
+                    column.origin_name = table.columns.get(&id).cloned();
+                }
+                column
+            })
+            .collect();
+        Arc::new(columns)
+    }
 }
 
-impl ConcatsqlConn for RefCell<postgres::Client> {
+impl ConcatsqlConn for PostgresConn {
     fn execute_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-    ) -> Result<()> {
-        if params.is_empty() {
-            match self.borrow_mut().batch_execute(&query) {
-                Ok(_) => Ok(()),
-                Err(e) => Error::new(error_level, "exec error", &e),
-            }
+        prepared: bool,
+    ) -> Result<ExecResult> {
+        let result = if params.is_empty() && !prepared {
+            // The simple protocol runs batches and reports the count of each statement.
+            self.borrow_mut()?.simple_query(&query).map(|messages| {
+                messages
+                    .iter()
+                    .filter_map(|message| match message {
+                        SimpleQueryMessage::CommandComplete(rows) => Some(*rows),
+                        _ => None,
+                    })
+                    .last()
+                    .unwrap_or(0)
+            })
         } else {
             let params = params
                 .iter()
-                .map(|value| to_sql!(value))
+                .map(|value| value as &(dyn ToSql + Sync))
                 .collect::<Vec<_>>();
-            match self.borrow_mut().execute(&query as &str, &params[..]) {
-                Ok(_) => Ok(()),
-                Err(e) => Error::new(error_level, "exec error", &e),
-            }
+            let mut client = self.borrow_mut()?;
+            self.prepare(&mut client, &query)
+                .and_then(|statement| client.execute(&statement, &params[..]))
+        };
+        match result {
+            Ok(rows_affected) => Ok(ExecResult::new(rows_affected, None)),
+            Err(e) => Error::new(error_level, "exec error", &e).map(|_| ExecResult::default()),
         }
     }
 
@@ -71,86 +150,181 @@ impl ConcatsqlConn for RefCell<postgres::Client> {
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-        callback: &mut dyn FnMut(&[(&str, Option<&str>)]) -> bool,
+        callback: &mut dyn FnMut(&Row) -> bool,
     ) -> Result<()> {
-        let params = params
-            .iter()
-            .map(|value| to_sql!(value))
-            .collect::<Vec<_>>();
-        let rows = match self.borrow_mut().query(&query as &str, &params[..]) {
-            Ok(result) => result,
+        let mut client = self.borrow_mut()?;
+        let params = params.iter().map(|value| value as &dyn ToSql);
+        let statement = match self.prepare(&mut client, &query) {
+            Ok(statement) => statement,
+            Err(e) => return Error::new(error_level, "exec error", &e),
+        };
+        let columns = self.columns(&mut client, statement.columns());
+        let mut rows = match client.query_raw(&statement, params) {
+            Ok(rows) => rows,
             Err(e) => return Error::new(error_level, "exec error", &e),
         };
-        let mut pairs = Vec::new();
 
-        for row in rows {
-            for (index, col) in row.columns().iter().enumerate() {
-                let value = row.get_to_string(index);
-                pairs.push((col.name().to_string(), value));
+        loop {
+            let row = match rows.next() {
+                Ok(Some(row)) => row,
+                Ok(None) => break,
+                Err(e) => return Error::new(error_level, "exec error", &e),
+            };
+            let row = Row::from_values(
+                columns.clone(),
+                (0..row.len()).map(|index| row.get_value(index)),
+            );
+            // The client discards the remaining rows before its next query.
+            if !callback(&row) {
+                break;
             }
         }
 
-        let pairs: Vec<(&str, Option<&str>)> =
-            pairs.iter().map(|p| (&*p.0, p.1.as_deref())).collect();
-        if !pairs.is_empty() && !callback(&pairs) {
-            return Error::new(error_level, "exec error", "query aborted");
-        }
-
         Ok(())
     }
 
-    fn rows_inner<'a, 'r>(
+    fn rows_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-    ) -> Result<Vec<Row<'r>>> {
+        _prepared: bool,
+    ) -> Result<Vec<Row>> {
         let params = params
             .iter()
-            .map(|value| to_sql!(value))
+            .map(|value| value as &(dyn ToSql + Sync))
             .collect::<Vec<_>>();
-        let result = match self.borrow_mut().query(&query as &str, &params[..]) {
+        let mut client = self.borrow_mut()?;
+        let statement = match self.prepare(&mut client, &query) {
+            Ok(statement) => statement,
+            Err(e) => return Error::new(error_level, "exec error", &e).map(|_| Vec::new()),
+        };
+        let columns = self.columns(&mut client, statement.columns());
+        let result = match client.query(&statement, &params[..]) {
             Ok(result) => result,
             Err(e) => return Error::new(error_level, "exec error", &e).map(|_| Vec::new()),
         };
-        let mut rows: Vec<Row> = Vec::new();
 
-        // First row
-        if let Some(first_row) = result.first() {
-            let column_len = first_row.columns().len();
-            let columns = first_row
-                .columns()
-                .iter()
-                .map(|col| col.name().to_string())
-                .collect();
-            let mut row = Row::new(columns);
-            for index in 0..column_len {
-                unsafe {
-                    row.insert(
-                        &*(row.column(index) as *const str),
-                        first_row.get_to_string(index),
-                    );
-                }
+        let rows = result
+            .iter()
+            .map(|row| {
+                Row::from_values(
+                    columns.clone(),
+                    (0..row.len()).map(|index| row.get_value(index)),
+                )
+            })
+            .collect();
+
+        Ok(rows)
+    }
+
+    fn query_iter_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &ErrorLevel,
+        _prepared: bool,
+    ) -> Result<Box<dyn QueryRows + 'c>> {
+        let mut lease = self.client.lend()?;
+        // The rows are dropped before the lease, see `PostgresRows`.
+        let client = unsafe { lease.get() };
+        let params = params.iter().map(|value| value as &dyn ToSql);
+        let statement = match self.prepare(client, &query) {
+            Ok(statement) => statement,
+            Err(e) => {
+                return Error::new(error_level, "exec error", &e)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>)
             }
-            rows.push(row);
+        };
+        let columns = self.columns(client, statement.columns());
+        match client.query_raw(&statement, params) {
+            Ok(rows) => Ok(Box::new(PostgresRows {
+                inner: Some((rows, lease)),
+                columns,
+                error_level: *error_level,
+            })),
+            Err(e) => Error::new(error_level, "exec error", &e)
+                .map(|_| Box::new(std::iter::empty()) as Box<_>),
         }
+    }
 
-        // Or later
-        for result_row in result.iter().skip(1) {
-            let column_len = result_row.columns().len();
-            let mut row = Row::new(rows[0].columns());
-            for index in 0..column_len {
-                unsafe {
-                    row.insert(
-                        &*(rows[0].column(index) as *const str),
-                        result_row.get_to_string(index),
-                    );
-                }
+    fn result_sets_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &ErrorLevel,
+    ) -> Result<Box<dyn Iterator<Item = Result<ResultSet>> + 'c>> {
+        let result = if params.is_empty() {
+            simple_result_sets(&mut *self.borrow_mut()?, &query)
+        } else {
+            let params = params.iter().map(|value| value as &dyn ToSql);
+            let mut client = self.borrow_mut()?;
+            self.prepare(&mut client, &query)
+                .and_then(|statement| {
+                    let columns = self.columns(&mut client, statement.columns());
+                    extended_result_set(&mut client, &statement, columns, params)
+                })
+                .map(|set| vec![set])
+        };
+        match result {
+            Ok(result_sets) => Ok(Box::new(result_sets.into_iter().map(Ok))),
+            Err(e) => Error::new(error_level, "exec error", &e)
+                .map(|_| Box::new(std::iter::empty()) as Box<_>),
+        }
+    }
+
+    fn execute_many_inner<'a>(
+        &self,
+        query: &str,
+        params: &mut dyn Iterator<Item = Vec<Value<'a>>>,
+        error_level: &ErrorLevel,
+    ) -> Result<u64> {
+        let mut client = self.borrow_mut()?;
+        let statement = match self.prepare(&mut client, query) {
+            Ok(statement) => statement,
+            Err(e) => return Error::new(error_level, "exec error", &e).map(|_| 0),
+        };
+        let count = statement.params().len();
+
+        let mut total = 0;
+        for row in params {
+            if row.len() != count {
+                return param_count_error(error_level, count, row.len()).map(|_| total);
+            }
+            let row = row
+                .iter()
+                .map(|value| value as &(dyn ToSql + Sync))
+                .collect::<Vec<_>>();
+            match client.execute(&statement, &row[..]) {
+                Ok(rows_affected) => total += rows_affected,
+                Err(e) => return Error::new(error_level, "exec error", &e).map(|_| total),
             }
-            rows.push(row);
         }
+        Ok(total)
+    }
 
-        Ok(rows)
+    fn prepare_inner(&self, query: &str, error_level: &ErrorLevel) -> Result<usize> {
+        match self.prepare(&mut *self.borrow_mut()?, query) {
+            Ok(statement) => Ok(statement.params().len()),
+            Err(e) => Error::new(error_level, "exec error", &e).map(|_| 0),
+        }
+    }
+
+    fn pin_statement(&self, query: &str) {
+        self.cache.borrow_mut().pin(query);
+    }
+
+    fn unpin_statement(&self, query: &str) {
+        self.cache.borrow_mut().unpin(query);
+    }
+
+    fn set_statement_cache_capacity(&self, capacity: usize) {
+        self.cache.borrow_mut().set_capacity(capacity);
+    }
+
+    fn clear_statement_cache(&self) {
+        self.cache.borrow_mut().clear();
+        self.tables.borrow_mut().clear();
     }
 
     fn close(&self) {
@@ -163,39 +337,191 @@ impl ConcatsqlConn for RefCell<postgres::Client> {
     }
 }
 
-trait GetToString {
-    fn get_to_string(&self, index: usize) -> Option<String>;
+/// Reads the rows of a query one at a time as they arrive.
+///
+/// The client is taken from the connection until the rows run out or the iterator is dropped.
+struct PostgresRows<'c> {
+    // The rows come first so that they are dropped before the lease.
+    inner: Option<(RowIter<'c>, Lease<'c, Client>)>,
+    columns: Arc<Columns>,
+    error_level: ErrorLevel,
+}
+
+impl Iterator for PostgresRows<'_> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rows, _) = self.inner.as_mut()?;
+        match rows.next() {
+            Ok(Some(row)) => Some(Ok(Row::from_values(
+                self.columns.clone(),
+                (0..row.len()).map(|index| row.get_value(index)),
+            ))),
+            Ok(None) => {
+                self.inner = None;
+                None
+            }
+            Err(e) => {
+                self.inner = None;
+                Error::new(&self.error_level, "exec error", &e)
+                    .err()
+                    .map(Err)
+            }
+        }
+    }
+}
+
+impl QueryRows for PostgresRows<'_> {
+    fn columns(&mut self) -> &[Column] {
+        &self.columns
+    }
+}
+
+/// Runs a batch over the simple query protocol, which allows several statements
+/// but returns every value as text.
+fn simple_result_sets(
+    client: &mut Client,
+    query: &str,
+) -> std::result::Result<Vec<ResultSet>, postgres::Error> {
+    let mut result_sets = Vec::new();
+    let mut columns: Arc<Columns> = Arc::default();
+    let mut rows = Vec::new();
+    for message in client.simple_query(query)? {
+        match message {
+            SimpleQueryMessage::RowDescription(description) => {
+                columns = Arc::new(
+                    description
+                        .iter()
+                        .map(|col| Column::new(col.name()))
+                        .collect(),
+                );
+            }
+            SimpleQueryMessage::Row(row) => {
+                let values = (0..row.len()).map(|index| match row.get(index) {
+                    Some(text) => simple_value(text),
+                    None => (Value::Null, None),
+                });
+                rows.push(Row::from_rendered(columns.clone(), values));
+            }
+            SimpleQueryMessage::CommandComplete(rows_affected) => {
+                let columns = std::mem::take(&mut columns);
+                let rows = std::mem::take(&mut rows);
+                result_sets.push(ResultSet::new(columns, rows, rows_affected));
+            }
+            _ => {}
+        }
+    }
+    Ok(result_sets)
+}
+
+/// A value of the simple protocol, which sends `bytea` as `\x` hex text.
+///
+/// Text in that form is read as its bytes and keeps the hex as its text form.
+fn simple_value(text: &str) -> (Value<'static>, Option<String>) {
+    match text.strip_prefix("\\x").and_then(from_hex) {
+        Some(bytes) => (Value::Bytes(bytes), Some(text.to_string())),
+        None => (Value::Text(Cow::Owned(text.to_string())), None),
+    }
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    let digit = |b: u8| char::from(b).to_digit(16);
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| match *pair {
+            [high, low] => Some((digit(high)? << 4 | digit(low)?) as u8),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Runs a single statement with parameters over the extended query protocol.
+fn extended_result_set<'p>(
+    client: &mut Client,
+    statement: &Statement,
+    columns: Arc<Columns>,
+    params: impl ExactSizeIterator<Item = &'p dyn ToSql>,
+) -> std::result::Result<ResultSet, postgres::Error> {
+    let mut iter = client.query_raw(statement, params)?;
+    let mut rows = Vec::new();
+    while let Some(row) = iter.next()? {
+        rows.push(Row::from_values(
+            columns.clone(),
+            (0..row.len()).map(|index| row.get_value(index)),
+        ));
+    }
+    let rows_affected = iter.rows_affected().unwrap_or_default();
+    Ok(ResultSet::new(columns, rows, rows_affected))
+}
+
+/// The metadata a result reports about a column; PostgreSQL does not report nullability.
+fn column(col: &postgres::Column) -> Column {
+    let mut column = Column::new(col.name());
+    column.decl_type = Some(col.type_().name().to_string());
+    // `numeric(p, s)` is stored as `((p << 16) | s) + 4`, or -1 without a precision.
+    if *col.type_() == Type::NUMERIC && col.type_modifier() >= 4 {
+        let modifier = (col.type_modifier() - 4) as u32;
+        column.precision = Some(modifier >> 16);
+        column.scale = Some(modifier & 0xffff);
+    }
+    column
+}
+
+/// Looks up the name of a table and of its columns, or `None` if the catalog cannot be read.
+fn table(client: &mut Client, oid: u32) -> Option<Table> {
+    let rows = client
+        .query(
+            "SELECT c.relname, a.attnum, a.attname FROM pg_class c \
+             JOIN pg_attribute a ON a.attrelid = c.oid \
+             WHERE c.oid = $1 AND a.attnum > 0 AND NOT a.attisdropped",
+            &[&oid],
+        )
+        .ok()?;
+    Some(Table {
+        name: rows.first()?.get(0),
+        columns: rows.iter().map(|row| (row.get(1), row.get(2))).collect(),
+    })
+}
+
+trait GetValue {
+    fn get_value(&self, index: usize) -> Value<'static>;
 }
-impl GetToString for postgres::row::Row {
-    fn get_to_string(&self, index: usize) -> Option<String> {
+impl GetValue for postgres::row::Row {
+    fn get_value(&self, index: usize) -> Value<'static> {
         if let Ok(value) = self.try_get::<usize, String>(index) {
-            Some(value)
+            Value::Text(Cow::Owned(value))
         } else if let Ok(value) = self.try_get::<usize, i32>(index) {
-            Some(value.to_string())
+            Value::I32(value)
         } else if let Ok(value) = self.try_get::<usize, i64>(index) {
-            Some(value.to_string())
+            Value::I64(value)
         } else if let Ok(value) = self.try_get::<usize, u32>(index) {
-            Some(value.to_string())
+            Value::I64(i64::from(value))
         } else if let Ok(value) = self.try_get::<usize, f32>(index) {
-            Some(value.to_string())
+            Value::F32(value)
         } else if let Ok(value) = self.try_get::<usize, f64>(index) {
-            Some(value.to_string())
+            Value::F64(value)
         } else if let Ok(value) = self.try_get::<usize, bool>(index) {
-            Some(value.to_string())
+            Value::Text(Cow::Owned(value.to_string()))
         } else if let Ok(value) = self.try_get::<usize, i8>(index) {
-            Some(value.to_string())
+            Value::I32(i32::from(value))
         } else if let Ok(value) = self.try_get::<usize, i16>(index) {
-            Some(value.to_string())
-        } else if let Ok(value) = self.try_get::<usize, std::net::IpAddr>(index) {
-            Some(value.to_string())
+            Value::I32(i32::from(value))
+        } else if let Ok(value) = self.try_get::<usize, Network>(index) {
+            value.0
+        } else if let Ok(value) = self.try_get::<usize, MacAddr>(index) {
+            Value::MacAddr(value)
         } else if let Ok(value) = self.try_get::<usize, Vec<u8>>(index) {
-            Some(crate::parser::to_hex(&value))
+            Value::Bytes(value)
         } else if let Ok(value) = self.try_get::<usize, Uuid>(index) {
-            Some(value.simple().to_string())
+            Value::Text(Cow::Owned(value.simple().to_string()))
         } else if let Ok(value) = self.try_get::<usize, SystemTime>(index) {
-            Some(value.to_string())
+            Value::Time(value)
+        } else if let Ok(Interval(value)) = self.try_get::<usize, Interval>(index) {
+            Value::Interval(value)
+        } else if let Ok(NumericText(value)) = self.try_get::<usize, NumericText>(index) {
+            Value::Text(Cow::Owned(value))
         } else {
-            None
+            Value::Null
         }
     }
 }
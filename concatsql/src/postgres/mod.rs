@@ -1,6 +1,7 @@
 //! Interface to [PostgreSQL](https://www.postgresql.org/) of ConcatSQL.
 
 pub(crate) mod connection;
+mod types;
 
 use crate::connection::Connection;
 use crate::Result;
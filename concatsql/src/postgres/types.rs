@@ -0,0 +1,359 @@
+//! Conversions between [`Value`] and PostgreSQL types.
+
+extern crate postgres_sys as postgres;
+
+use bytes::{BufMut, BytesMut};
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::net::{max_prefix, IpNetwork, MacAddr};
+use crate::value::{Int128Repr, Value};
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+macro_rules! dispatch {
+    ($value:expr, $method:ident, $ty:expr, $out:expr) => {
+        match $value {
+            Value::Null => Ok(IsNull::Yes),
+            Value::I32(value) => value.$method($ty, $out),
+            Value::I64(value) => value.$method($ty, $out),
+            Value::I128(value, Int128Repr::Text) => Numeric::from_i128(*value).$method($ty, $out),
+            Value::I128(value, Int128Repr::Blob) => (&value.to_be_bytes()[..]).$method($ty, $out),
+            Value::U128(value, Int128Repr::Text) => Numeric::from_u128(*value).$method($ty, $out),
+            Value::U128(value, Int128Repr::Blob) => (&value.to_be_bytes()[..]).$method($ty, $out),
+            Value::F32(value) => value.$method($ty, $out),
+            Value::F64(value) => value.$method($ty, $out),
+            Value::Text(value) => value.$method($ty, $out),
+            Value::Bytes(value) => value.$method($ty, $out),
+            Value::IpAddr(value) => value.$method($ty, $out),
+            Value::IpNetwork(value) => value.$method($ty, $out),
+            Value::MacAddr(value) => value.$method($ty, $out),
+            Value::Time(value) => value.$method($ty, $out),
+            Value::Interval(value) => Interval(*value).$method($ty, $out),
+        }
+    };
+}
+
+/// `Value::Null` binds as a `NULL` of any type: the server infers the parameter type
+/// from the statement, so the NULL takes on the type of the column or expression
+/// it is compared with or stored in.
+/// Other values are type checked by the `ToSql` implementation of their content.
+impl ToSql for Value<'_> {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        dispatch!(self, to_sql, ty, out)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn to_sql_checked(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        dispatch!(self, to_sql_checked, ty, out)
+    }
+}
+
+/// A PostgreSQL `interval`.
+///
+/// A month is read as 30 days, so a year is 360 days as in the text format.
+#[derive(Debug)]
+pub(crate) struct Interval(pub(crate) chrono::Duration);
+
+impl ToSql for Interval {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        let microseconds = self.0.num_microseconds().ok_or("interval out of range")?;
+        out.put_i64(microseconds);
+        out.put_i32(0); // days
+        out.put_i32(0); // months
+        Ok(IsNull::No)
+    }
+
+    accepts!(INTERVAL);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        if raw.len() != 16 {
+            return Err("invalid message length".into());
+        }
+        let microseconds = i64::from_be_bytes(raw[0..8].try_into()?);
+        let days = i32::from_be_bytes(raw[8..12].try_into()?);
+        let months = i32::from_be_bytes(raw[12..16].try_into()?);
+        Ok(Interval(
+            chrono::Duration::microseconds(microseconds)
+                + chrono::Duration::days(i64::from(days) + i64::from(months) * 30),
+        ))
+    }
+
+    accepts!(INTERVAL);
+}
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+/// A PostgreSQL `numeric` holding an integer.
+#[derive(Debug)]
+pub(crate) struct Numeric {
+    negative: bool,
+    magnitude: u128,
+}
+
+impl Numeric {
+    pub(crate) fn from_i128(value: i128) -> Self {
+        Numeric {
+            negative: value < 0,
+            magnitude: value.unsigned_abs(),
+        }
+    }
+
+    pub(crate) fn from_u128(value: u128) -> Self {
+        Numeric {
+            negative: false,
+            magnitude: value,
+        }
+    }
+}
+
+impl ToSql for Numeric {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        // Base 10000 digits, least significant first.
+        let mut digits = Vec::new();
+        let mut magnitude = self.magnitude;
+        while magnitude != 0 {
+            digits.push((magnitude % 10000) as i16);
+            magnitude /= 10000;
+        }
+        let weight = digits.len().saturating_sub(1) as i16;
+        // Trailing zero digits are implied by the weight.
+        let trailing_zeros = digits.iter().take_while(|&&digit| digit == 0).count();
+
+        out.put_i16((digits.len() - trailing_zeros) as i16);
+        out.put_i16(weight);
+        out.put_u16(if self.negative {
+            NUMERIC_NEG
+        } else {
+            NUMERIC_POS
+        });
+        out.put_u16(0); // dscale
+        for digit in digits[trailing_zeros..].iter().rev() {
+            out.put_i16(*digit);
+        }
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+    to_sql_checked!();
+}
+
+/// The text form of a PostgreSQL `numeric`.
+#[derive(Debug)]
+pub(crate) struct NumericText(pub(crate) String);
+
+impl<'a> FromSql<'a> for NumericText {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        if raw.len() < 8 {
+            return Err("invalid message length".into());
+        }
+        let ndigits = i16::from_be_bytes(raw[0..2].try_into()?) as usize;
+        let weight = i32::from(i16::from_be_bytes(raw[2..4].try_into()?));
+        let sign = u16::from_be_bytes(raw[4..6].try_into()?);
+        let dscale = u16::from_be_bytes(raw[6..8].try_into()?) as usize;
+        if raw.len() != 8 + ndigits * 2 {
+            return Err("invalid message length".into());
+        }
+        let digit = |i: i32| -> i16 {
+            if 0 <= i && (i as usize) < ndigits {
+                let at = 8 + i as usize * 2;
+                i16::from_be_bytes([raw[at], raw[at + 1]])
+            } else {
+                0
+            }
+        };
+
+        let mut text = String::new();
+        match sign {
+            NUMERIC_POS => {}
+            NUMERIC_NEG => text.push('-'),
+            NUMERIC_NAN => return Ok(NumericText("NaN".into())),
+            NUMERIC_PINF => return Ok(NumericText("Infinity".into())),
+            NUMERIC_NINF => return Ok(NumericText("-Infinity".into())),
+            _ => return Err("invalid numeric sign".into()),
+        }
+        if weight < 0 {
+            text.push('0');
+        } else {
+            write!(text, "{}", digit(0))?;
+            for i in 1..=weight {
+                write!(text, "{:04}", digit(i))?;
+            }
+        }
+        if dscale > 0 {
+            let mut fraction = String::new();
+            let mut i = weight + 1;
+            while fraction.len() < dscale {
+                write!(fraction, "{:04}", digit(i))?;
+                i += 1;
+            }
+            fraction.truncate(dscale);
+            text.push('.');
+            text.push_str(&fraction);
+        }
+        Ok(NumericText(text))
+    }
+
+    accepts!(NUMERIC);
+}
+
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = 3;
+
+/// Binds as `inet`, or as `cidr` when the server expects one; host bits must then be zero.
+impl ToSql for IpNetwork {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        let is_cidr = *ty == Type::CIDR;
+        match self.addr() {
+            IpAddr::V4(addr) => {
+                out.put_slice(&[PGSQL_AF_INET, self.prefix(), is_cidr as u8, 4]);
+                out.put_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                out.put_slice(&[PGSQL_AF_INET6, self.prefix(), is_cidr as u8, 16]);
+                out.put_slice(&addr.octets());
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    accepts!(INET, CIDR);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for IpNetwork {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        let (network, _) = inet_from_sql(raw)?;
+        Ok(network)
+    }
+
+    accepts!(INET, CIDR);
+}
+
+/// Returns the network and whether it is a `cidr`.
+fn inet_from_sql(raw: &[u8]) -> Result<(IpNetwork, bool), BoxError> {
+    if raw.len() < 4 {
+        return Err("invalid message length".into());
+    }
+    let (family, prefix, is_cidr, addr) = (raw[0], raw[1], raw[2] != 0, &raw[4..]);
+    let addr = match (family, addr.len()) {
+        (PGSQL_AF_INET, 4) => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(addr)?)),
+        (PGSQL_AF_INET6, 16) => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(addr)?)),
+        _ => return Err("invalid inet address".into()),
+    };
+    let network = IpNetwork::new(addr, prefix).ok_or("invalid inet prefix")?;
+    Ok((network, is_cidr))
+}
+
+/// A PostgreSQL `inet` or `cidr` column value.
+///
+/// Like the server's own output, `inet` single hosts are read as a bare `IpAddr`.
+#[derive(Debug)]
+pub(crate) struct Network(pub(crate) Value<'static>);
+
+impl<'a> FromSql<'a> for Network {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        let (network, is_cidr) = inet_from_sql(raw)?;
+        Ok(Network(
+            if !is_cidr && network.prefix() == max_prefix(&network.addr()) {
+                Value::IpAddr(network.addr())
+            } else {
+                Value::IpNetwork(network)
+            },
+        ))
+    }
+
+    accepts!(INET, CIDR);
+}
+
+impl ToSql for MacAddr {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        out.put_slice(&self.0);
+        Ok(IsNull::No)
+    }
+
+    accepts!(MACADDR);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for MacAddr {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        Ok(MacAddr(raw.try_into()?))
+    }
+
+    accepts!(MACADDR);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric_text(numeric: Numeric) -> String {
+        let mut buf = BytesMut::new();
+        numeric.to_sql(&Type::NUMERIC, &mut buf).unwrap();
+        NumericText::from_sql(&Type::NUMERIC, &buf).unwrap().0
+    }
+
+    #[test]
+    fn numeric() {
+        for value in [0, 1, -1, 10000, -10000, 123_456_789, i128::MIN, i128::MAX] {
+            assert_eq!(numeric_text(Numeric::from_i128(value)), value.to_string());
+        }
+        assert_eq!(
+            numeric_text(Numeric::from_u128(u128::MAX)),
+            u128::MAX.to_string()
+        );
+
+        // 12.50
+        let raw = [0, 2, 0, 0, 0, 0, 0, 2, 0, 12, 0x13, 0x88];
+        assert_eq!(
+            NumericText::from_sql(&Type::NUMERIC, &raw).unwrap().0,
+            "12.50"
+        );
+        // -0.000012
+        let raw = [0, 1, 0xff, 0xfe, 0x40, 0, 0, 6, 0x04, 0xb0];
+        assert_eq!(
+            NumericText::from_sql(&Type::NUMERIC, &raw).unwrap().0,
+            "-0.000012"
+        );
+    }
+
+    #[test]
+    fn inet() {
+        let net: IpNetwork = "10.1.0.0/16".parse().unwrap();
+        let mut buf = BytesMut::new();
+        net.to_sql(&Type::CIDR, &mut buf).unwrap();
+        assert_eq!(&buf[..], [2, 16, 1, 4, 10, 1, 0, 0]);
+        assert_eq!(IpNetwork::from_sql(&Type::CIDR, &buf).unwrap(), net);
+        assert_eq!(
+            Network::from_sql(&Type::CIDR, &buf).unwrap().0,
+            Value::IpNetwork(net)
+        );
+
+        let host: IpNetwork = "::1".parse().unwrap();
+        let mut buf = BytesMut::new();
+        host.to_sql(&Type::INET, &mut buf).unwrap();
+        assert_eq!(buf.len(), 20);
+        assert_eq!(IpNetwork::from_sql(&Type::INET, &buf).unwrap(), host);
+        assert_eq!(
+            Network::from_sql(&Type::INET, &buf).unwrap().0,
+            Value::IpAddr(host.addr())
+        );
+
+        assert!(IpNetwork::from_sql(&Type::INET, &[2, 33, 0, 4, 10, 1, 0, 0]).is_err());
+    }
+}
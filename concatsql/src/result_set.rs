@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use crate::column::{Column, Columns};
+use crate::row::Row;
+
+/// The result of one statement of a batch, returned by [`Connection::result_sets`](./struct.Connection.html#method.result_sets).
+///
+/// Statements that return no rows, such as `INSERT`, still produce a result set
+/// with no columns and the number of rows they changed.
+#[derive(Debug, PartialEq)]
+pub struct ResultSet {
+    columns: Arc<Columns>,
+    rows: Vec<Row>,
+    rows_affected: u64,
+}
+
+impl ResultSet {
+    #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+    pub(crate) fn new(columns: Arc<Columns>, rows: Vec<Row>, rows_affected: u64) -> Self {
+        ResultSet {
+            columns,
+            rows,
+            rows_affected,
+        }
+    }
+
+    /// The columns of the result, known even when there are no rows.
+    #[inline]
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    #[inline]
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    #[inline]
+    pub fn into_rows(self) -> Vec<Row> {
+        self.rows
+    }
+
+    /// The number of rows inserted, updated or deleted by the statement.
+    ///
+    /// PostgreSQL reports the number of rows returned for `SELECT` as well.
+    #[inline]
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
+
+/// The outcome of a statement run by [`Connection::execute_with_result`](./struct.Connection.html#method.execute_with_result).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExecResult {
+    rows_affected: u64,
+    last_insert_id: Option<i64>,
+}
+
+impl ExecResult {
+    #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+    pub(crate) fn new(rows_affected: u64, last_insert_id: Option<i64>) -> Self {
+        ExecResult {
+            rows_affected,
+            last_insert_id,
+        }
+    }
+
+    /// The number of rows inserted, updated or deleted by the statement.
+    ///
+    /// For a batch, MySQL and PostgreSQL report its last statement, and SQLite the last
+    /// `INSERT`, `UPDATE` or `DELETE` that ran, even if statements that change nothing follow it.
+    /// MySQL counts only the rows that actually changed, unless the client sets `CLIENT_FOUND_ROWS`.
+    #[inline]
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+
+    /// The `ROWID` (SQLite) or `AUTO_INCREMENT` value (MySQL) generated by the statement.
+    ///
+    /// `None` if the statement inserted nothing, and always on PostgreSQL, where
+    /// `INSERT ... RETURNING` with [`Connection::query_one`](./struct.Connection.html#method.query_one) does the same.
+    /// SQLite tells an insert apart by a new rowid, so it also reports `None` for an insert
+    /// that reuses the rowid of the one before it, such as after deleting the newest row.
+    #[inline]
+    pub fn last_insert_id(&self) -> Option<i64> {
+        self.last_insert_id
+    }
+}
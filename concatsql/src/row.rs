@@ -1,43 +1,125 @@
+use std::convert::TryFrom;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
+use crate::column::{Column, Columns};
 use crate::error::Error;
-use indexmap::map::IndexMap;
+use crate::net::{IpNetwork, MacAddr};
+use crate::parser::parse_interval;
+use crate::value::Value;
+
+/// A column value and its text form, rendered on first use.
+#[derive(Clone, Debug)]
+struct Field {
+    value: Value<'static>,
+    text: OnceLock<String>,
+}
 
-type IndexMapPairs<'a> = IndexMap<&'a str, Option<String>>;
+impl Field {
+    fn text(&self) -> Option<&str> {
+        match &self.value {
+            Value::Null => None,
+            Value::Text(text) => Some(text),
+            value => Some(
+                self.text
+                    .get_or_init(|| value.to_text().unwrap_or_default().into_owned()),
+            ),
+        }
+    }
+}
+
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
 
 /// A single result row of a query.
-#[derive(Debug, PartialEq)]
-pub struct Row<'a> {
-    columns: Arc<[String]>,
-    pairs: IndexMapPairs<'a>,
+///
+/// Every column is kept, in the order of the result, even when several share a name.
+/// The rows of a result share their columns, so cloning a row only copies its values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    columns: Arc<Columns>,
+    fields: Box<[Field]>,
 }
 
-impl<'a> Row<'a> {
-    pub(crate) fn new(columns: Arc<[String]>) -> Self {
-        Self {
-            columns,
-            pairs: IndexMap::new(),
-        }
+impl Row {
+    /// Builds a row sharing the columns of the other rows of a result.
+    #[cfg(any(feature = "mysql", feature = "postgres", test))]
+    pub(crate) fn from_values(
+        columns: Arc<Columns>,
+        values: impl IntoIterator<Item = Value<'static>>,
+    ) -> Self {
+        let fields = values
+            .into_iter()
+            .map(|value| Field {
+                value,
+                text: OnceLock::new(),
+            })
+            .collect();
+        Row { columns, fields }
+    }
+
+    /// Builds a row whose values may come with the text the database rendered for them.
+    #[cfg(any(feature = "sqlite", feature = "postgres"))]
+    pub(crate) fn from_rendered(
+        columns: Arc<Columns>,
+        values: impl IntoIterator<Item = (Value<'static>, Option<String>)>,
+    ) -> Self {
+        let fields = values
+            .into_iter()
+            .map(|(value, text)| Field {
+                value,
+                text: text.map_or_else(OnceLock::new, OnceLock::from),
+            })
+            .collect();
+        Row { columns, fields }
     }
 
     #[inline]
     pub(crate) fn column(&self, index: usize) -> &str {
-        &self.columns[index]
+        &self.columns[index].name
     }
 
+    #[cfg(any(feature = "sqlite", feature = "mysql"))]
     #[inline]
-    pub(crate) fn columns(&self) -> Arc<[String]> {
+    pub(crate) fn shared_columns(&self) -> Arc<Columns> {
         self.columns.clone()
     }
 
+    /// The position of the first column called `name`, or of the first `table.column` match.
+    fn position(&self, name: &str) -> Option<usize> {
+        self.columns
+            .position(name)
+            .or_else(|| self.qualified(name).next())
+    }
+
+    /// The positions of the columns that `table.column` names, by table and original name or alias.
+    fn qualified<'r>(&'r self, name: &'r str) -> impl Iterator<Item = usize> + 'r {
+        name.rsplit_once('.')
+            .into_iter()
+            .flat_map(move |(table, name)| {
+                self.columns
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, column)| {
+                        column.table.as_deref() == Some(table)
+                            && (column.origin_name.as_deref() == Some(name) || column.name == name)
+                    })
+                    .map(|(index, _)| index)
+            })
+    }
+
     #[inline]
-    pub(crate) fn insert(&mut self, key: &'a str, value: Option<String>) {
-        self.pairs.insert(key, value);
+    fn field<T: Get>(&self, key: T) -> Option<&Field> {
+        self.fields.get(key.index(self)?)
     }
 
     /// Get the value of a column of the result row.
     ///
+    /// Values that are not text are formatted on first access; blobs are hex-encoded.
+    ///
     /// # Examples
     ///
     /// ```
@@ -49,11 +131,53 @@ impl<'a> Row<'a> {
     /// }
     /// ```
     pub fn get<T: Get>(&self, key: T) -> Option<&str> {
-        key.get(&self.pairs)
+        self.field(key)?.text()
+    }
+
+    /// Get the value of a column as returned by the database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// for row in &conn.rows("SELECT 42, '42', NULL").unwrap() {
+    ///     assert_eq!(row.get_value(0), Some(&Value::I64(42)));
+    ///     assert_eq!(row.get_value(1), Some(&Value::Text("42".into())));
+    ///     assert_eq!(row.get_value(2), Some(&Value::Null));
+    ///     assert_eq!(row.get_value(3), None);
+    /// }
+    /// ```
+    pub fn get_value<T: Get>(&self, key: T) -> Option<&Value<'static>> {
+        Some(&self.field(key)?.value)
+    }
+
+    /// Get the raw bytes of a column.
+    ///
+    /// Returns the contents of `BLOB`/`bytea`/binary columns as stored, and the UTF-8 bytes of text columns.
+    /// Returns `None` for `NULL` and other types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// for row in &conn.rows("SELECT X'00FF', 'AB', 1").unwrap() {
+    ///     assert_eq!(row.get_bytes(0), Some(&[0x00, 0xff][..]));
+    ///     assert_eq!(row.get_bytes(1), Some(&b"AB"[..]));
+    ///     assert_eq!(row.get_bytes(2), None);
+    /// }
+    /// ```
+    pub fn get_bytes<T: Get>(&self, key: T) -> Option<&[u8]> {
+        match &self.field(key)?.value {
+            Value::Bytes(bytes) => Some(bytes),
+            Value::Text(text) => Some(text.as_bytes()),
+            _ => None,
+        }
     }
 
     /// Transforms and gets the columns of the result row.  
-    /// &#x26a0;&#xfe0f; If column is NULL then execute `U::from_str("")`.
+    /// A NULL column is `None` for `Option<U>` and [`Error::UnexpectedNull`](./enum.Error.html#variant.UnexpectedNull) otherwise.
     ///
     /// # Examples
     ///
@@ -70,53 +194,138 @@ impl<'a> Row<'a> {
     ///     let one: u8 = row.get_into(0).unwrap();
     ///     assert_eq!(one, 1u8);
     /// }
+    ///
+    /// for row in &conn.rows("SELECT NULL AS age").unwrap() {
+    ///     assert_eq!(row.get_into::<_, Option<i32>>("age"), Ok(None));
+    ///     assert_eq!(
+    ///         row.get_into::<_, i32>("age"),
+    ///         Err(concatsql::Error::UnexpectedNull("age".into()))
+    ///     );
+    /// }
     /// ```
-    #[inline]
     pub fn get_into<T: Get, U: FromSql>(&self, key: T) -> Result<U, Error> {
-        key.get_into::<U>(&self.pairs)
+        let index = key.index(self).ok_or(Error::ColumnNotFound)?;
+        match &self.fields[index].value {
+            Value::Null => {
+                U::from_null().ok_or_else(|| Error::UnexpectedNull(self.column(index).to_string()))
+            }
+            value => U::from_value(value),
+        }
+    }
+
+    /// Decodes the columns of the row positionally into a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// for row in &conn.rows("SELECT 42, 'Alice', NULL").unwrap() {
+    ///     let (id, name, score): (i64, String, Option<f64>) = row.get_tuple().unwrap();
+    ///     assert_eq!((id, name.as_str(), score), (42, "Alice", None));
+    /// }
+    /// ```
+    #[inline]
+    pub fn get_tuple<T: FromRow>(&self) -> Result<T, Error> {
+        T::from_row(self)
     }
 
     /// Return the number of columns.
     #[inline]
     pub fn column_count(&self) -> usize {
-        self.pairs.len()
+        self.fields.len()
     }
 
     /// Determines if there are any values in the row.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.pairs.len() == 0
+        self.fields.is_empty()
     }
 
     /// Get the column name.  
     #[inline]
     pub fn column_name<T: Get>(&self, key: T) -> Option<&str> {
-        key.get_key(&self.pairs)
+        Some(self.column(key.index(self)?))
     }
 
-    /// Get all the column names.  
+    /// Get all the column names, including duplicates.  
     #[inline]
     pub fn column_names(&self) -> Vec<&str> {
-        self.pairs.keys().copied().collect::<Vec<_>>()
-        //self.columns.iter().map(AsRef::as_ref).collect::<Vec<_>>()
+        (0..self.column_count())
+            .map(|index| self.column(index))
+            .collect()
     }
 
+    /// Get the positions of every column called `name`.
+    ///
+    /// Lookups by name return the first of them; use the positions to reach the others.
+    /// If no column is called `name`, a `table.column` name selects the columns read from
+    /// that table, as [`Get`](./trait.Get.html) does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// for row in &conn.rows("SELECT 1 AS id, 2 AS id").unwrap() {
+    ///     assert_eq!(row.column_indices("id"), [0, 1]);
+    ///     assert_eq!(row.get("id"), Some("1"));
+    ///     assert_eq!(row.get(1), Some("2"));
+    /// }
+    /// ```
+    pub fn column_indices(&self, name: &str) -> Vec<usize> {
+        let indices: Vec<usize> = (0..self.column_count())
+            .filter(|&index| self.column(index) == name)
+            .collect();
+        if indices.is_empty() {
+            self.qualified(name).collect()
+        } else {
+            indices
+        }
+    }
+
+    /// Get the values of every column called `name`, in column order.
+    ///
+    /// `name` selects the same columns as in [`column_indices`](#method.column_indices).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use concatsql::prelude::*;
+    /// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+    /// for row in &conn.rows("SELECT 1 AS id, NULL AS id, 3 AS id").unwrap() {
+    ///     assert_eq!(row.get_all("id"), [Some("1"), None, Some("3")]);
+    /// }
+    /// ```
+    pub fn get_all(&self, name: &str) -> Vec<Option<&str>> {
+        self.column_indices(name)
+            .into_iter()
+            .map(|index| self.fields[index].text())
+            .collect()
+    }
+
+    /// Get the metadata of the columns, shared by all rows of the result.
     #[inline]
-    pub fn iter(&self) -> RowIter {
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    #[inline]
+    pub fn iter(&self) -> RowIter<'_> {
         RowIter { row: self, now: 0 }
     }
 }
 
-impl<'a, T: Get> std::ops::Index<T> for Row<'a> {
+impl<T: Get> std::ops::Index<T> for Row {
     type Output = str;
     fn index(&self, key: T) -> &Self::Output {
-        key.get(&self.pairs).unwrap()
+        self.get(key).unwrap()
     }
 }
 
 #[doc(hidden)]
 pub struct RowIter<'a> {
-    row: &'a Row<'a>,
+    row: &'a Row,
     now: usize,
 }
 
@@ -132,7 +341,7 @@ impl<'a> Iterator for RowIter<'a> {
     }
 }
 
-impl<'a> IntoIterator for &'a Row<'a> {
+impl<'a> IntoIterator for &'a Row {
     type Item = &'a str;
     type IntoIter = RowIter<'a>;
     fn into_iter(self) -> Self::IntoIter {
@@ -141,93 +350,107 @@ impl<'a> IntoIterator for &'a Row<'a> {
 }
 
 /// A trait implemented by types that can index into columns of a row.
+///
+/// A name selects the first column with that name. If there is none, a `table.column`
+/// name selects the first column read from that table, as reported by [`Column::table`].
 pub trait Get {
-    fn get<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str>;
-    fn get_into<U: FromSql>(&self, pairs: &IndexMapPairs) -> Result<U, Error>;
-    fn get_key<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str>;
+    /// Returns the position of the column in `row`.
+    fn index(&self, row: &Row) -> Option<usize>;
 }
 
 impl Get for str {
-    fn get<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        pairs.get(self)?.as_deref()
-    }
-
-    fn get_into<U: FromSql>(&self, pairs: &IndexMapPairs) -> Result<U, Error> {
-        U::from_sql(
-            pairs
-                .get(self)
-                .ok_or(Error::ColumnNotFound)?
-                .as_deref()
-                .unwrap_or(""),
-        )
-    }
-
-    fn get_key<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        Some(pairs.get_key_value(self)?.0)
+    fn index(&self, row: &Row) -> Option<usize> {
+        row.position(self)
     }
 }
 
 impl Get for String {
-    fn get<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        pairs.get(&**self)?.as_deref()
-    }
-
-    fn get_into<U: FromSql>(&self, pairs: &IndexMapPairs) -> Result<U, Error> {
-        U::from_sql(
-            pairs
-                .get(&**self)
-                .ok_or(Error::ColumnNotFound)?
-                .as_deref()
-                .unwrap_or(""),
-        )
-    }
-
-    fn get_key<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        Some(pairs.get_key_value(&**self)?.0)
+    fn index(&self, row: &Row) -> Option<usize> {
+        row.position(self)
     }
 }
 
 impl Get for usize {
-    fn get<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        pairs.get_index(*self)?.1.as_deref()
-    }
-
-    fn get_into<U: FromSql>(&self, pairs: &IndexMapPairs) -> Result<U, Error> {
-        U::from_sql(
-            pairs
-                .get_index(*self)
-                .ok_or(Error::ColumnNotFound)?
-                .1
-                .as_deref()
-                .unwrap_or(""),
-        )
-    }
-
-    fn get_key<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        Some(pairs.get_index(*self)?.0)
+    fn index(&self, row: &Row) -> Option<usize> {
+        if *self < row.column_count() {
+            Some(*self)
+        } else {
+            None
+        }
     }
 }
 
-impl<'b, T> Get for &'b T
+impl<T> Get for &T
 where
     T: Get + ?Sized,
 {
-    fn get<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        T::get(self, pairs)
+    fn index(&self, row: &Row) -> Option<usize> {
+        T::index(self, row)
     }
+}
 
-    fn get_into<U: FromSql>(&self, pairs: &IndexMapPairs) -> Result<U, Error> {
-        T::get_into(self, pairs)
-    }
+/// A trait for types that can be built from a result row.
+///
+/// Usually derived with [`#[derive(FromRow)]`](derive.FromRow.html).
+/// Tuples of up to 12 [`FromSql`] types decode the columns positionally.
+pub trait FromRow: Sized {
+    fn from_row(row: &Row) -> Result<Self, Error>;
+}
 
-    fn get_key<'a>(&self, pairs: &'a IndexMapPairs) -> Option<&'a str> {
-        T::get_key(self, pairs)
-    }
+/// Decodes the columns positionally; extra columns are ignored.
+macro_rules! from_row_tuple_impl {
+    ( $( ( $($t:ident $i:tt),+ ) )* ) => {$(
+        impl<$($t: FromSql),+> FromRow for ($($t,)+) {
+            fn from_row(row: &Row) -> Result<Self, Error> {
+                Ok(($(row.get_into::<_, $t>($i)?,)+))
+            }
+        }
+    )*};
+}
+from_row_tuple_impl! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
 }
 
 /// Parse a value from a sql string.
 pub trait FromSql: Sized {
     fn from_sql(s: &str) -> Result<Self, Error>;
+
+    /// Converts a column value as returned by the database.
+    ///
+    /// The default implementation parses the text form of the value with `from_sql`.
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        Self::from_sql(&value.to_text().unwrap_or_default())
+    }
+
+    /// Converts a NULL column, or returns `None` if the type cannot represent NULL.
+    fn from_null() -> Option<Self> {
+        None
+    }
+}
+
+impl<T: FromSql> FromSql for Option<T> {
+    fn from_sql(s: &str) -> Result<Self, Error> {
+        T::from_sql(s).map(Some)
+    }
+
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        T::from_value(value).map(Some)
+    }
+
+    fn from_null() -> Option<Self> {
+        Some(None)
+    }
 }
 
 macro_rules! from_sql_impl {
@@ -242,13 +465,9 @@ macro_rules! from_sql_impl {
     ( $($t:ty,)* ) => { from_sql_impl! { $( $t ),* } };
 }
 from_sql_impl! {
-    std::net::IpAddr,
     std::net::SocketAddr,
     bool,
     char,
-    f32, f64,
-    i8, i16, i32, i64, i128, isize,
-    u8, u16, u32, u64, u128, usize,
     std::ffi::OsString,
     std::net::Ipv4Addr,
     std::net::Ipv6Addr,
@@ -270,14 +489,175 @@ from_sql_impl! {
     String,
 }
 
+macro_rules! from_sql_impl_integer {
+    ( $($t:ty),* ) => {$(
+        from_sql_impl_integer! { @blob $t, _bytes => Err(Error::ParseError) }
+    )*};
+    ( $($t:ty,)* ) => { from_sql_impl_integer! { $( $t ),* } };
+    ( @blob $t:ty, $bytes:ident => $from_blob:expr ) => {
+        impl FromSql for $t {
+            #[doc(hidden)]
+            fn from_sql(s: &str) -> Result<Self, Error> {
+                Self::from_str(s).map_err(|_|Error::ParseError)
+            }
+
+            #[doc(hidden)]
+            fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+                match value {
+                    Value::I32(value) => Self::try_from(*value).map_err(|_| Error::ParseError),
+                    Value::I64(value) => Self::try_from(*value).map_err(|_| Error::ParseError),
+                    Value::I128(value, _) => Self::try_from(*value).map_err(|_| Error::ParseError),
+                    Value::U128(value, _) => Self::try_from(*value).map_err(|_| Error::ParseError),
+                    Value::Bytes($bytes) => $from_blob,
+                    value => Self::from_sql(&value.to_text().unwrap_or_default()),
+                }
+            }
+        }
+    };
+}
+from_sql_impl_integer! {
+    i8, i16, i32, i64, isize,
+    u8, u16, u32, u64, usize,
+}
+// `AsBlob` binds 128-bit integers as 16 big-endian bytes.
+from_sql_impl_integer! {
+    @blob i128, bytes => <[u8; 16]>::try_from(&bytes[..])
+        .map(i128::from_be_bytes)
+        .map_err(|_| Error::ParseError)
+}
+from_sql_impl_integer! {
+    @blob u128, bytes => <[u8; 16]>::try_from(&bytes[..])
+        .map(u128::from_be_bytes)
+        .map_err(|_| Error::ParseError)
+}
+
+macro_rules! from_sql_impl_float {
+    ( $($t:ty),* ) => {$(
+        impl FromSql for $t {
+            #[doc(hidden)]
+            fn from_sql(s: &str) -> Result<Self, Error> {
+                Self::from_str(s).map_err(|_|Error::ParseError)
+            }
+
+            #[doc(hidden)]
+            fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+                match value {
+                    Value::I32(value) => Ok(*value as $t),
+                    Value::I64(value) => Ok(*value as $t),
+                    Value::F32(value) => Ok(*value as $t),
+                    Value::F64(value) => Ok(*value as $t),
+                    Value::Bytes(_) => Err(Error::ParseError),
+                    value => Self::from_sql(&value.to_text().unwrap_or_default()),
+                }
+            }
+        }
+    )*};
+    ( $($t:ty,)* ) => { from_sql_impl_float! { $( $t ),* } };
+}
+from_sql_impl_float! {
+    f32, f64,
+}
+
+impl FromSql for std::net::IpAddr {
+    #[doc(hidden)]
+    fn from_sql(s: &str) -> Result<Self, Error> {
+        Self::from_str(s).map_err(|_| Error::ParseError)
+    }
+
+    #[doc(hidden)]
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        match value {
+            Value::IpAddr(value) => Ok(*value),
+            Value::Bytes(_) => Err(Error::ParseError),
+            value => Self::from_sql(&value.to_text().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromSql for IpNetwork {
+    #[doc(hidden)]
+    fn from_sql(s: &str) -> Result<Self, Error> {
+        s.parse()
+    }
+
+    #[doc(hidden)]
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        match value {
+            Value::IpAddr(value) => Ok(IpNetwork::from(*value)),
+            Value::IpNetwork(value) => Ok(*value),
+            Value::Bytes(_) => Err(Error::ParseError),
+            value => Self::from_sql(&value.to_text().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromSql for MacAddr {
+    #[doc(hidden)]
+    fn from_sql(s: &str) -> Result<Self, Error> {
+        s.parse()
+    }
+
+    #[doc(hidden)]
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        match value {
+            Value::MacAddr(value) => Ok(*value),
+            Value::Bytes(_) => Err(Error::ParseError),
+            value => Self::from_sql(&value.to_text().unwrap_or_default()),
+        }
+    }
+}
+
+/// Binary columns are copied as is and text gives its UTF-8 bytes.
 impl FromSql for Vec<u8> {
     #[doc(hidden)]
     fn from_sql(s: &str) -> Result<Self, Error> {
-        (0..s.len())
-            .step_by(2)
-            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| ()))
-            .collect::<Result<Vec<u8>, ()>>()
-            .map_err(|_| Error::ParseError)
+        Ok(s.as_bytes().to_vec())
+    }
+
+    #[doc(hidden)]
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        match value {
+            Value::Bytes(value) => Ok(value.clone()),
+            Value::Text(value) => Self::from_sql(value),
+            _ => Err(Error::ParseError),
+        }
+    }
+}
+
+/// Parses `[-]hh:mm:ss[.ffffff]`, PostgreSQL interval output or a number of seconds.
+impl FromSql for chrono::Duration {
+    #[doc(hidden)]
+    fn from_sql(s: &str) -> Result<Self, Error> {
+        parse_interval(s).ok_or(Error::ParseError)
+    }
+
+    #[doc(hidden)]
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        match value {
+            Value::Interval(value) => Ok(*value),
+            Value::Bytes(_) => Err(Error::ParseError),
+            value => Self::from_sql(&value.to_text().unwrap_or_default()),
+        }
+    }
+}
+
+/// Parses `hh:mm:ss[.ffffff]`, PostgreSQL interval output or a number of seconds.
+/// Negative intervals are a `ParseError`.
+impl FromSql for std::time::Duration {
+    #[doc(hidden)]
+    fn from_sql(s: &str) -> Result<Self, Error> {
+        parse_interval(s)
+            .and_then(|interval| interval.to_std().ok())
+            .ok_or(Error::ParseError)
+    }
+
+    #[doc(hidden)]
+    fn from_value(value: &Value<'_>) -> Result<Self, Error> {
+        match value {
+            Value::Interval(value) => value.to_std().map_err(|_| Error::ParseError),
+            Value::Bytes(_) => Err(Error::ParseError),
+            value => Self::from_sql(&value.to_text().unwrap_or_default()),
+        }
     }
 }
 
@@ -307,15 +687,21 @@ mod tests {
     #[test]
     #[allow(clippy::needless_borrow, clippy::needless_borrows_for_generic_args)]
     fn row() {
-        let mut row = Row::new(
-            ["key1", "key2", "key3", "ABC"]
-                .iter()
-                .map(ToString::to_string)
-                .collect(),
+        let row = Row::from_values(
+            Arc::new(
+                ["key1", "key2", "key3", "ABC", "bytes"]
+                    .iter()
+                    .map(|name| Column::new(*name))
+                    .collect(),
+            ),
+            vec![
+                Value::Text("value".into()),
+                Value::Null,
+                Value::Text("42".into()),
+                Value::Text("414243".into()),
+                Value::Bytes(vec![0xab, 0xcd]),
+            ],
         );
-        row.insert("key1", Some("value".to_string()));
-        row.insert("key2", None);
-        row.insert("key3", Some("42".to_string()));
 
         assert_eq!(row.get("key1"), Some("value"));
         assert_eq!(row.get("key1").unwrap(), "value");
@@ -326,7 +712,7 @@ mod tests {
         assert_eq!(row.get(0).unwrap(), "value");
         assert_eq!(row.get(1), None);
         assert_eq!(row.get(2), Some("42"));
-        assert_eq!(row.get(3), None);
+        assert_eq!(row.get(5), None);
 
         assert_eq!(
             row.get_into::<&str, String>("key1"),
@@ -335,13 +721,29 @@ mod tests {
         assert_eq!(row.get_into::<&str, i32>("key3"), Ok(42));
         assert_eq!(row.get_into::<&str, usize>("key3"), Ok(42));
         assert_eq!(row.get_into("key3"), Ok(42));
-        assert_eq!(row.get_into("key2"), Ok(String::new()));
+        assert_eq!(
+            row.get_into::<_, String>("key2"),
+            Err(Error::UnexpectedNull("key2".to_string()))
+        );
         assert_eq!(row.get_into("key1"), Ok(String::from("value")));
         assert_eq!(row.get_into::<usize, String>(0), Ok(String::from("value")));
         assert_eq!(row.get_into::<usize, i32>(2), Ok(42));
         assert_eq!(row.get_into::<usize, usize>(2), Ok(42));
         assert_eq!(row.get_into(2), Ok(42));
-        assert_eq!(row.get_into(1), Ok(String::new()));
+        assert_eq!(
+            row.get_into::<_, String>(1),
+            Err(Error::UnexpectedNull("key2".to_string()))
+        );
+        assert_eq!(row.get_into::<_, Option<String>>(1), Ok(None));
+        assert_eq!(
+            row.get_into::<_, Option<String>>(0),
+            Ok(Some(String::from("value")))
+        );
+        assert_eq!(row.get_into::<_, Option<i32>>("key3"), Ok(Some(42)));
+        assert_eq!(
+            row.get_into::<_, Option<i32>>("key1"),
+            Err(Error::ParseError)
+        );
         assert_eq!(row.get_into(0), Ok(String::from("value")));
         assert!(row.get_into::<&str, u32>("key1").is_err());
         assert!(row.get_into::<&str, u32>("key2").is_err());
@@ -356,13 +758,29 @@ mod tests {
         assert_eq!(row.get_into::<_, i32>("key3"), Ok(42));
         assert_eq!(row.get_into::<_, usize>("key3"), Ok(42));
         assert_eq!(row.get_into("key3"), Ok(42));
-        assert_eq!(row.get_into("key2"), Ok(String::new()));
+        assert_eq!(
+            row.get_into::<_, String>("key2"),
+            Err(Error::UnexpectedNull("key2".to_string()))
+        );
         assert_eq!(row.get_into("key1"), Ok(String::from("value")));
         assert_eq!(row.get_into::<_, String>(0), Ok(String::from("value")));
         assert_eq!(row.get_into::<_, i32>(2), Ok(42));
         assert_eq!(row.get_into::<_, usize>(2), Ok(42));
         assert_eq!(row.get_into(2), Ok(42));
-        assert_eq!(row.get_into(1), Ok(String::new()));
+        assert_eq!(
+            row.get_into::<_, String>(1),
+            Err(Error::UnexpectedNull("key2".to_string()))
+        );
+        assert_eq!(row.get_into::<_, Option<String>>(1), Ok(None));
+        assert_eq!(
+            row.get_into::<_, Option<String>>(0),
+            Ok(Some(String::from("value")))
+        );
+        assert_eq!(row.get_into::<_, Option<i32>>("key3"), Ok(Some(42)));
+        assert_eq!(
+            row.get_into::<_, Option<i32>>("key1"),
+            Err(Error::ParseError)
+        );
         assert_eq!(row.get_into(0), Ok(String::from("value")));
         assert!(row.get_into::<_, u32>("key1").is_err());
         assert!(row.get_into::<_, u32>("key2").is_err());
@@ -373,7 +791,7 @@ mod tests {
         assert!(row.get_into::<_, u32>(99).is_err());
         assert!(row.get_into::<_, String>(99).is_err());
 
-        assert_eq!(row.column_count(), 3);
+        assert_eq!(row.column_count(), 5);
 
         assert!(row.column_names().contains(&"key1"));
         assert!(row.column_names().contains(&"key2"));
@@ -392,11 +810,12 @@ mod tests {
         assert_eq!(row.get(&String::from("key1")), Some("value"));
         assert_eq!(row.get(&&String::from("key1")), Some("value"));
 
-        row.insert("ABC", Some("414243".to_string()));
-        assert_eq!(
-            row.get_into::<_, Vec<u8>>("ABC"),
-            Ok(vec![b'A', b'B', b'C'])
-        );
+        assert_eq!(row.get_into::<_, Vec<u8>>("ABC"), Ok(b"414243".to_vec()));
+        assert_eq!(row.get_bytes("ABC"), Some(&b"414243"[..]));
+        assert_eq!(row.get_bytes("key2"), None);
+        assert_eq!(row.get("bytes"), Some("ABCD"));
+        assert_eq!(row.get_bytes("bytes"), Some(&[0xab, 0xcd][..]));
+        assert_eq!(row.get_into::<_, Vec<u8>>("bytes"), Ok(vec![0xab, 0xcd]));
         assert!(row.get_into::<_, i8>("ABC").is_err());
         assert!(row.get_into::<_, u8>("ABC").is_err());
         assert!(row.get_into::<_, i16>("ABC").is_err());
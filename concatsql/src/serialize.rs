@@ -0,0 +1,124 @@
+//! `Serialize` implementations, enabled by the `serde` feature.
+//!
+//! A row becomes a map from column names to values. Columns sharing a name produce
+//! repeated keys, so alias them in the query if the format does not allow that.
+
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+use crate::column::Column;
+use crate::result_set::ResultSet;
+use crate::row::Row;
+use crate::value::Value;
+
+/// Numbers, text and bytes keep their type; other values use the text form of [`Row::get`].
+impl Serialize for Value<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_none(),
+            Value::I32(value) => serializer.serialize_i32(*value),
+            Value::I64(value) => serializer.serialize_i64(*value),
+            Value::I128(value, _) => serializer.serialize_i128(*value),
+            Value::U128(value, _) => serializer.serialize_u128(*value),
+            Value::F32(value) => serializer.serialize_f32(*value),
+            Value::F64(value) => serializer.serialize_f64(*value),
+            Value::Text(value) => serializer.serialize_str(value),
+            Value::Bytes(value) => serializer.serialize_bytes(value),
+            value => serializer.serialize_str(&value.to_text().unwrap_or_default()),
+        }
+    }
+}
+
+impl Serialize for Row {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.column_count()))?;
+        for index in 0..self.column_count() {
+            map.serialize_entry(self.column(index), self.get_value(index).unwrap())?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Column {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Column", 7)?;
+        state.serialize_field("name", self.name())?;
+        state.serialize_field("decl_type", &self.decl_type())?;
+        state.serialize_field("nullable", &self.nullable())?;
+        state.serialize_field("table", &self.table())?;
+        state.serialize_field("origin_name", &self.origin_name())?;
+        state.serialize_field("precision", &self.precision())?;
+        state.serialize_field("scale", &self.scale())?;
+        state.end()
+    }
+}
+
+impl Serialize for ResultSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ResultSet", 3)?;
+        state.serialize_field("columns", self.columns())?;
+        state.serialize_field("rows", self.rows())?;
+        state.serialize_field("rows_affected", &self.rows_affected())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "sqlite")]
+mod tests {
+    use serde_json::json;
+
+    #[test]
+    fn row() {
+        let conn = crate::sqlite::open(":memory:").unwrap();
+        let rows = conn
+            .rows("SELECT 1 AS id, 'Alice' AS name, 2.5 AS score, NULL AS note, X'00FF' AS data")
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&rows[0]).unwrap(),
+            json!({"id": 1, "name": "Alice", "score": 2.5, "note": null, "data": [0, 255]})
+        );
+        assert_eq!(
+            serde_json::to_string(&rows[0]).unwrap(),
+            r#"{"id":1,"name":"Alice","score":2.5,"note":null,"data":[0,255]}"#
+        );
+
+        let rows = conn.rows("SELECT 1 AS n UNION ALL SELECT 2").unwrap();
+        assert_eq!(
+            serde_json::to_value(&rows[..]).unwrap(),
+            json!([{"n": 1}, {"n": 2}])
+        );
+        assert_eq!(
+            serde_json::to_value(crate::Value::IpAddr([127, 0, 0, 1].into())).unwrap(),
+            json!("127.0.0.1")
+        );
+    }
+
+    #[test]
+    fn result_set() {
+        let conn = crate::sqlite::open(":memory:").unwrap();
+        let sets = conn
+            .result_sets(
+                "CREATE TABLE t (id INTEGER NOT NULL); INSERT INTO t VALUES (7); SELECT id FROM t;",
+            )
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&sets[1..]).unwrap(),
+            json!([
+                {"columns": [], "rows": [], "rows_affected": 1},
+                {
+                    "columns": [{
+                        "name": "id",
+                        "decl_type": "INTEGER",
+                        "nullable": false,
+                        "table": "t",
+                        "origin_name": "id",
+                        "precision": null,
+                        "scale": null,
+                    }],
+                    "rows": [{"id": 7}],
+                    "rows_affected": 0,
+                },
+            ])
+        );
+    }
+}
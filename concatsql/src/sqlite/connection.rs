@@ -1,15 +1,20 @@
 extern crate sqlite3_sys as ffi;
 
 use std::borrow::Cow;
-use std::cell::Cell;
-use std::ffi::{c_int, c_void, CStr, CString};
+use std::cell::{Cell, RefCell};
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
 use std::path::Path;
 use std::ptr::{self, NonNull};
+use std::sync::Arc;
 
-use crate::connection::{ConcatsqlConn, ConnKind, Connection};
+use crate::cache::StatementCache;
+use crate::column::{parse_precision, Column, Columns};
+use crate::connection::{ConcatsqlConn, ConnKind, Connection, QueryRows};
 use crate::error::{Error, ErrorLevel};
+use crate::result_set::{ExecResult, ResultSet};
 use crate::row::Row;
-use crate::value::{SystemTimeToString, Value};
+use crate::statement::param_count_error;
+use crate::value::{interval_to_string, Int128Repr, SystemTimeToString, Value};
 use crate::Result;
 
 /// Open a read-write connection to a new or existing database.
@@ -33,8 +38,12 @@ pub fn open<T: AsRef<Path>>(path: T, openflags: i32) -> Result<Connection> {
 
     match open_result {
         ffi::SQLITE_OK => Ok(Connection {
-            conn: Box::new(unsafe { NonNull::new_unchecked(conn_ptr) }),
+            conn: Box::new(SqliteConn {
+                db: unsafe { NonNull::new_unchecked(conn_ptr) },
+                cache: RefCell::default(),
+            }),
             error_level: Cell::new(ErrorLevel::default()),
+            in_transaction: Cell::new(false),
         }),
         _ => {
             unsafe {
@@ -45,21 +54,78 @@ pub fn open<T: AsRef<Path>>(path: T, openflags: i32) -> Result<Connection> {
     }
 }
 
-impl ConcatsqlConn for NonNull<ffi::sqlite3> {
+/// A SQLite connection and the statements it keeps prepared.
+pub(crate) struct SqliteConn {
+    db: NonNull<ffi::sqlite3>,
+    /// Statements not in use; one being stepped is taken out until it is released.
+    cache: RefCell<StatementCache<Statement>>,
+}
+
+/// A prepared statement, finalized when dropped.
+struct Statement(NonNull<ffi::sqlite3_stmt>);
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sqlite3_finalize(self.0.as_ptr());
+        }
+    }
+}
+
+impl SqliteConn {
+    #[inline]
+    fn as_ptr(&self) -> *mut ffi::sqlite3 {
+        self.db.as_ptr()
+    }
+
+    /// Takes the statement for `query` from the cache, or prepares it.
+    unsafe fn prepare(&self, query: &CStr, stmt: &mut *mut ffi::sqlite3_stmt) -> c_int {
+        if let Some(cached) = self.cache.borrow_mut().take(&query.to_string_lossy()) {
+            *stmt = cached.0.as_ptr();
+            std::mem::forget(cached);
+            return ffi::SQLITE_OK;
+        }
+        ffi::sqlite3_prepare_v2(self.as_ptr(), query.as_ptr(), -1, stmt, ptr::null_mut())
+    }
+
+    /// Resets `stmt` and returns it to the cache, which finalizes any statement it displaces.
+    unsafe fn release(&self, query: &CStr, stmt: *mut ffi::sqlite3_stmt) {
+        if let Some(stmt) = NonNull::new(stmt) {
+            ffi::sqlite3_reset(stmt.as_ptr());
+            ffi::sqlite3_clear_bindings(stmt.as_ptr());
+            self.cache
+                .borrow_mut()
+                .insert(query.to_string_lossy().into_owned(), Statement(stmt));
+        }
+    }
+}
+
+impl ConcatsqlConn for SqliteConn {
     fn execute_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-    ) -> Result<()> {
+        prepared: bool,
+    ) -> Result<ExecResult> {
         let query = match CString::new(query.as_bytes()) {
             Ok(string) => string,
-            _ => return Error::new(error_level, "invalid query", query),
+            _ => {
+                return Error::new(error_level, "invalid query", query)
+                    .map(|_| ExecResult::default())
+            }
         };
 
         let mut stmt = ptr::null_mut();
+        // Compared afterwards, so that statements which change or insert nothing report it.
+        let (total_changes, last_insert_rowid) = unsafe {
+            (
+                ffi::sqlite3_total_changes(self.as_ptr()),
+                ffi::sqlite3_last_insert_rowid(self.as_ptr()),
+            )
+        };
 
-        if params.is_empty() {
+        if params.is_empty() && !prepared {
             let mut errmsg = ptr::null_mut();
             unsafe {
                 ffi::sqlite3_exec(
@@ -72,57 +138,57 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
             }
 
             if errmsg.is_null() {
-                return Ok(());
+                return Ok(unsafe { exec_result(self.as_ptr(), total_changes, last_insert_rowid) });
             } else {
                 unsafe {
-                    ffi::sqlite3_finalize(stmt);
+                    self.release(&query, stmt);
                     ffi::sqlite3_free(errmsg as *mut _);
                     return Error::new(
                         error_level,
                         "exec error",
                         CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
-                    );
+                    )
+                    .map(|_| ExecResult::default());
                 }
             }
         }
 
         unsafe {
-            let result = ffi::sqlite3_prepare_v2(
-                self.as_ptr(),
-                query.as_ptr(),
-                -1,
-                &mut stmt,
-                ptr::null_mut(),
-            );
+            let result = self.prepare(&query, &mut stmt);
 
             if result != ffi::SQLITE_OK {
-                ffi::sqlite3_finalize(stmt);
+                self.release(&query, stmt);
                 return Error::new(
                     error_level,
                     "exec error",
                     CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
-                );
+                )
+                .map(|_| ExecResult::default());
             }
 
-            bind_all(stmt, params, error_level)?;
+            if let Some(error) = bind_all(stmt, params, error_level) {
+                self.release(&query, stmt);
+                return error.map(|_| ExecResult::default());
+            }
 
             loop {
                 match ffi::sqlite3_step(stmt) {
                     ffi::SQLITE_DONE => break,
                     ffi::SQLITE_ROW => (), // Do nothing
                     _ => {
-                        ffi::sqlite3_finalize(stmt);
+                        self.release(&query, stmt);
                         return Error::new(
                             error_level,
                             "exec error",
                             CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
-                        );
+                        )
+                        .map(|_| ExecResult::default());
                     }
                 }
             }
 
-            ffi::sqlite3_finalize(stmt);
-            Ok(())
+            self.release(&query, stmt);
+            Ok(exec_result(self.as_ptr(), total_changes, last_insert_rowid))
         }
     }
 
@@ -131,7 +197,7 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-        callback: &mut dyn FnMut(&[(&str, Option<&str>)]) -> bool,
+        callback: &mut dyn FnMut(&Row) -> bool,
     ) -> Result<()> {
         let query = match CString::new(query.as_bytes()) {
             Ok(string) => string,
@@ -140,16 +206,10 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
         let mut stmt = ptr::null_mut();
 
         unsafe {
-            let result = ffi::sqlite3_prepare_v2(
-                self.as_ptr(),
-                query.as_ptr(),
-                -1,
-                &mut stmt,
-                ptr::null_mut(),
-            );
+            let result = self.prepare(&query, &mut stmt);
 
             if result != ffi::SQLITE_OK {
-                ffi::sqlite3_finalize(stmt);
+                self.release(&query, stmt);
                 return Error::new(
                     error_level,
                     "exec error",
@@ -157,24 +217,26 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
                 );
             }
 
-            bind_all(stmt, params, error_level)?;
+            if let Some(error) = bind_all(stmt, params, error_level) {
+                self.release(&query, stmt);
+                return error;
+            }
 
-            let column_count = ffi::sqlite3_column_count(stmt);
+            let mut shared = None;
 
             loop {
                 match ffi::sqlite3_step(stmt) {
                     ffi::SQLITE_DONE => break,
                     ffi::SQLITE_ROW => {
-                        let mut pairs = Vec::with_capacity(column_count as usize);
-                        pairs.storing(stmt, column_count);
-                        let pairs: Vec<(&str, Option<&str>)> =
-                            pairs.iter().map(|p| (p.0, p.1.as_deref())).collect();
-                        if !callback(&pairs) {
+                        // Read after stepping, which prepares the statement again if the schema changed.
+                        let columns = shared.get_or_insert_with(|| columns(stmt)).clone();
+                        let row = read_row(stmt, columns);
+                        if !callback(&row) {
                             break;
                         }
                     }
                     _ => {
-                        ffi::sqlite3_finalize(stmt);
+                        self.release(&query, stmt);
                         return Error::new(
                             error_level,
                             "exec error",
@@ -184,17 +246,18 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
                 }
             }
 
-            ffi::sqlite3_finalize(stmt);
+            self.release(&query, stmt);
             Ok(())
         }
     }
 
-    fn rows_inner<'a, 'r>(
+    fn rows_inner<'a>(
         &self,
         query: Cow<'a, str>,
         params: &[Value<'a>],
         error_level: &ErrorLevel,
-    ) -> Result<Vec<Row<'r>>> {
+        _prepared: bool,
+    ) -> Result<Vec<Row>> {
         let mut rows: Vec<Row> = Vec::new();
         let query = match CString::new(query.as_bytes()) {
             Ok(string) => string,
@@ -203,16 +266,10 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
         let mut stmt = ptr::null_mut();
 
         unsafe {
-            let result = ffi::sqlite3_prepare_v2(
-                self.as_ptr(),
-                query.as_ptr(),
-                -1,
-                &mut stmt,
-                ptr::null_mut(),
-            );
+            let result = self.prepare(&query, &mut stmt);
 
             if result != ffi::SQLITE_OK {
-                ffi::sqlite3_finalize(stmt);
+                self.release(&query, stmt);
                 return Error::new(
                     error_level,
                     "exec error",
@@ -221,28 +278,23 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
                 .map(|_| Vec::new());
             }
 
-            bind_all(stmt, params, error_level)?;
-
-            let column_count = ffi::sqlite3_column_count(stmt);
+            if let Some(error) = bind_all(stmt, params, error_level) {
+                self.release(&query, stmt);
+                return error.map(|_| Vec::new());
+            }
 
             // First row
             match ffi::sqlite3_step(stmt) {
                 ffi::SQLITE_DONE => {
-                    ffi::sqlite3_finalize(stmt);
+                    self.release(&query, stmt);
                     return Ok(rows);
                 }
                 ffi::SQLITE_ROW => {
-                    let mut pairs = Vec::with_capacity(column_count as usize);
-                    pairs.storing(stmt, column_count);
-                    let columns = pairs.iter().map(|(column, _)| column.to_string()).collect();
-                    let mut row = Row::new(columns);
-                    for (index, (_, value)) in pairs.into_iter().enumerate() {
-                        row.insert(&*(row.column(index) as *const str), value);
-                    }
-                    rows.push(row);
+                    // Read after stepping, which prepares the statement again if the schema changed.
+                    rows.push(read_row(stmt, columns(stmt)));
                 }
                 _ => {
-                    ffi::sqlite3_finalize(stmt);
+                    self.release(&query, stmt);
                     return Error::new(
                         error_level,
                         "exec error",
@@ -257,16 +309,10 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
                 match ffi::sqlite3_step(stmt) {
                     ffi::SQLITE_DONE => break,
                     ffi::SQLITE_ROW => {
-                        let mut pairs = Vec::with_capacity(column_count as usize);
-                        pairs.storing(stmt, column_count);
-                        let mut row = Row::new(rows[0].columns());
-                        for (index, (_, value)) in pairs.into_iter().enumerate() {
-                            row.insert(&*(rows[0].column(index) as *const str), value);
-                        }
-                        rows.push(row);
+                        rows.push(read_row(stmt, rows[0].shared_columns()));
                     }
                     _ => {
-                        ffi::sqlite3_finalize(stmt);
+                        self.release(&query, stmt);
                         return Error::new(
                             error_level,
                             "exec error",
@@ -277,12 +323,190 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
                 }
             }
 
-            ffi::sqlite3_finalize(stmt);
+            self.release(&query, stmt);
             Ok(rows)
         }
     }
 
+    fn query_iter_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &ErrorLevel,
+        _prepared: bool,
+    ) -> Result<Box<dyn QueryRows + 'c>> {
+        let query = match CString::new(query.as_bytes()) {
+            Ok(string) => string,
+            _ => {
+                return Error::new(error_level, "invalid query", query)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>)
+            }
+        };
+        let mut stmt = ptr::null_mut();
+
+        unsafe {
+            let result = self.prepare(&query, &mut stmt);
+
+            if result != ffi::SQLITE_OK {
+                self.release(&query, stmt);
+                return Error::new(
+                    error_level,
+                    "exec error",
+                    CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
+                )
+                .map(|_| Box::new(std::iter::empty()) as Box<_>);
+            }
+
+            if let Some(error) = bind_all(stmt, params, error_level) {
+                self.release(&query, stmt);
+                return error.map(|_| Box::new(std::iter::empty()) as Box<_>);
+            }
+
+            Ok(Box::new(SqliteRows {
+                conn: self,
+                query,
+                stmt,
+                pending: None,
+                columns: None,
+                error_level: *error_level,
+            }))
+        }
+    }
+
+    fn result_sets_inner<'c, 'a>(
+        &'c self,
+        query: Cow<'a, str>,
+        params: &[Value<'a>],
+        error_level: &ErrorLevel,
+    ) -> Result<Box<dyn Iterator<Item = Result<ResultSet>> + 'c>> {
+        let query = match CString::new(query.as_bytes()) {
+            Ok(string) => string,
+            _ => {
+                return Error::new(error_level, "invalid query", query)
+                    .map(|_| Box::new(std::iter::empty()) as Box<_>)
+            }
+        };
+
+        Ok(Box::new(SqliteResultSets {
+            conn: self,
+            query,
+            offset: 0,
+            params: params.iter().cloned().map(Value::into_owned).collect(),
+            param_offset: 0,
+            error_level: *error_level,
+        }))
+    }
+
+    fn execute_many_inner<'a>(
+        &self,
+        query: &str,
+        params: &mut dyn Iterator<Item = Vec<Value<'a>>>,
+        error_level: &ErrorLevel,
+    ) -> Result<u64> {
+        let query = match CString::new(query) {
+            Ok(string) => string,
+            _ => return Error::new(error_level, "invalid query", query).map(|_| 0),
+        };
+        let mut stmt = ptr::null_mut();
+
+        unsafe {
+            macro_rules! fail {
+                ($msg:expr, $detail:expr, $total:expr) => {{
+                    let error = Error::new(error_level, $msg, $detail);
+                    self.release(&query, stmt);
+                    return error.map(|_| $total);
+                }};
+            }
+
+            if self.prepare(&query, &mut stmt) != ffi::SQLITE_OK {
+                fail!(
+                    "exec error",
+                    CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
+                    0
+                );
+            }
+            let count = ffi::sqlite3_bind_parameter_count(stmt) as usize;
+
+            // The statement is reset and rebound for each set instead of prepared again.
+            let mut total = 0;
+            for row in params {
+                if row.len() != count {
+                    let error = param_count_error(error_level, count, row.len());
+                    self.release(&query, stmt);
+                    return error.map(|_| total);
+                }
+                let result = bind_params(stmt, &row);
+                if result != ffi::SQLITE_OK {
+                    fail!(
+                        "bind error",
+                        CStr::from_ptr(ffi::sqlite3_errstr(result)).to_string_lossy(),
+                        total
+                    );
+                }
+
+                let total_changes = ffi::sqlite3_total_changes(self.as_ptr());
+                loop {
+                    match ffi::sqlite3_step(stmt) {
+                        ffi::SQLITE_DONE => break,
+                        ffi::SQLITE_ROW => (), // Do nothing
+                        _ => fail!(
+                            "exec error",
+                            CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
+                            total
+                        ),
+                    }
+                }
+                total += changes(self.as_ptr(), total_changes);
+                ffi::sqlite3_reset(stmt);
+            }
+
+            self.release(&query, stmt);
+            Ok(total)
+        }
+    }
+
+    fn prepare_inner(&self, query: &str, error_level: &ErrorLevel) -> Result<usize> {
+        let query = match CString::new(query) {
+            Ok(string) => string,
+            _ => return Error::new(error_level, "invalid query", query).map(|_| 0),
+        };
+        let mut stmt = ptr::null_mut();
+
+        unsafe {
+            if self.prepare(&query, &mut stmt) != ffi::SQLITE_OK || stmt.is_null() {
+                let error = Error::new(
+                    error_level,
+                    "exec error",
+                    CStr::from_ptr(ffi::sqlite3_errmsg(self.as_ptr())).to_string_lossy(),
+                );
+                ffi::sqlite3_finalize(stmt);
+                return error.map(|_| 0);
+            }
+            let count = ffi::sqlite3_bind_parameter_count(stmt) as usize;
+            self.release(&query, stmt);
+            Ok(count)
+        }
+    }
+
+    fn pin_statement(&self, query: &str) {
+        self.cache.borrow_mut().pin(query);
+    }
+
+    fn unpin_statement(&self, query: &str) {
+        self.cache.borrow_mut().unpin(query);
+    }
+
+    fn set_statement_cache_capacity(&self, capacity: usize) {
+        self.cache.borrow_mut().set_capacity(capacity);
+    }
+
+    fn clear_statement_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
     fn close(&self) {
+        // Unfinalized statements keep the database open.
+        self.cache.borrow_mut().clear();
         unsafe {
             ffi::sqlite3_busy_handler(
                 self.as_ptr() as *const _ as *mut ffi::sqlite3,
@@ -303,111 +527,369 @@ impl ConcatsqlConn for NonNull<ffi::sqlite3> {
     }
 }
 
-trait Storing {
-    unsafe fn storing(&mut self, stmt: *mut ffi::sqlite3_stmt, column_count: i32);
+/// Steps a prepared statement one row at a time; released when done or dropped.
+struct SqliteRows<'c> {
+    conn: &'c SqliteConn,
+    query: CString,
+    stmt: *mut ffi::sqlite3_stmt,
+    /// The result of a step taken to read the columns, not yet returned as a row.
+    pending: Option<c_int>,
+    /// Read after the first step, once any schema change has been applied.
+    columns: Option<Arc<Columns>>,
+    error_level: ErrorLevel,
+}
+
+impl SqliteRows<'_> {
+    fn release(&mut self) {
+        if !self.stmt.is_null() {
+            unsafe {
+                self.conn.release(&self.query, self.stmt);
+            }
+            self.stmt = ptr::null_mut();
+        }
+    }
 }
-impl Storing for Vec<(&str, Option<String>)> {
-    unsafe fn storing(&mut self, stmt: *mut ffi::sqlite3_stmt, column_count: i32) {
-        for i in 0..column_count {
-            let column_name = {
-                let column_name = ffi::sqlite3_column_name(stmt, i);
-                CStr::from_ptr(column_name).to_str().unwrap()
+
+impl Iterator for SqliteRows<'_> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stmt.is_null() {
+            return None;
+        }
+        unsafe {
+            let result = match self.pending.take() {
+                Some(result) => result,
+                None => ffi::sqlite3_step(self.stmt),
             };
-            let value = {
-                match ffi::sqlite3_column_type(stmt, i) {
-                    ffi::SQLITE_BLOB => {
-                        let ptr = ffi::sqlite3_column_blob(stmt, i);
-                        let count = ffi::sqlite3_column_bytes(stmt, i) as usize;
-                        let bytes = std::slice::from_raw_parts::<u8>(ptr as *const u8, count);
-                        Some(crate::parser::to_hex(bytes))
-                    },
-                    ffi::SQLITE_INTEGER |
-                    ffi::SQLITE_FLOAT   |
-                    ffi::SQLITE_TEXT    => {
-                        let ptr = ffi::sqlite3_column_text(stmt, i) as *const _ as *mut i8;
-                        Some(CStr::from_ptr(ptr).to_str().unwrap().to_owned())
+            let stmt = self.stmt;
+            match result {
+                ffi::SQLITE_ROW => {
+                    let columns = self.columns.get_or_insert_with(|| columns(stmt)).clone();
+                    Some(Ok(read_row(stmt, columns)))
+                }
+                ffi::SQLITE_DONE => {
+                    self.columns.get_or_insert_with(|| columns(stmt));
+                    self.release();
+                    None
+                }
+                _ => {
+                    let error = Error::new(
+                        &self.error_level,
+                        "exec error",
+                        CStr::from_ptr(ffi::sqlite3_errmsg(self.conn.as_ptr())).to_string_lossy(),
+                    );
+                    self.release();
+                    error.err().map(Err)
+                }
+            }
+        }
+    }
+}
+
+impl QueryRows for SqliteRows<'_> {
+    fn columns(&mut self) -> &[Column] {
+        if self.columns.is_none() && !self.stmt.is_null() {
+            unsafe {
+                self.pending = Some(ffi::sqlite3_step(self.stmt));
+                self.columns = Some(columns(self.stmt));
+            }
+        }
+        self.columns.as_deref().map_or(&[], |columns| columns)
+    }
+}
+
+impl Drop for SqliteRows<'_> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// Prepares and runs the statements of a batch one at a time.
+struct SqliteResultSets<'c> {
+    conn: &'c SqliteConn,
+    query: CString,
+    /// Start of the next statement in `query`; past the end once done.
+    offset: usize,
+    params: Vec<Value<'static>>,
+    param_offset: usize,
+    error_level: ErrorLevel,
+}
+
+impl SqliteResultSets<'_> {
+    unsafe fn error(
+        &mut self,
+        stmt: *mut ffi::sqlite3_stmt,
+        err_msg: &str,
+        message: String,
+    ) -> Option<Result<ResultSet>> {
+        ffi::sqlite3_finalize(stmt);
+        self.offset = usize::MAX;
+        self.param_offset = self.params.len();
+        Error::new(&self.error_level, err_msg, message)
+            .err()
+            .map(Err)
+    }
+}
+
+impl Iterator for SqliteResultSets<'_> {
+    type Item = Result<ResultSet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let conn = self.conn.as_ptr();
+        let bytes = self.query.as_bytes();
+        unsafe {
+            // Skips whitespace and comments between statements, which prepare to nothing.
+            let stmt = loop {
+                if self.offset >= bytes.len() {
+                    if self.param_offset < self.params.len() {
+                        let message = format!(
+                            "expected {} parameters, got {}",
+                            self.param_offset,
+                            self.params.len()
+                        );
+                        return self.error(ptr::null_mut(), "bind error", message);
                     }
-                    _  /* ffi::SQLITE_NULL */ => None
+                    return None;
+                }
+                let start = self.query.as_ptr().add(self.offset);
+                let mut stmt = ptr::null_mut();
+                let mut tail = ptr::null();
+                if ffi::sqlite3_prepare_v2(conn, start, -1, &mut stmt, &mut tail) != ffi::SQLITE_OK
+                {
+                    let message = CStr::from_ptr(ffi::sqlite3_errmsg(conn))
+                        .to_string_lossy()
+                        .into_owned();
+                    return self.error(stmt, "exec error", message);
+                }
+                self.offset = tail.offset_from(self.query.as_ptr()) as usize;
+                if !stmt.is_null() {
+                    break stmt;
                 }
             };
-            self.push((column_name, value));
+
+            let count = ffi::sqlite3_bind_parameter_count(stmt) as usize;
+            let end = self.param_offset + count;
+            if end > self.params.len() {
+                let message = format!("expected {} parameters, got {}", end, self.params.len());
+                return self.error(stmt, "bind error", message);
+            }
+            let result = bind_params(stmt, &self.params[self.param_offset..end]);
+            self.param_offset = end;
+            if result != ffi::SQLITE_OK {
+                let message = CStr::from_ptr(ffi::sqlite3_errstr(result))
+                    .to_string_lossy()
+                    .into_owned();
+                return self.error(stmt, "bind error", message);
+            }
+
+            let columns = columns(stmt);
+            let total_changes = ffi::sqlite3_total_changes(conn);
+            let mut rows = Vec::new();
+            loop {
+                match ffi::sqlite3_step(stmt) {
+                    ffi::SQLITE_DONE => break,
+                    ffi::SQLITE_ROW => {
+                        rows.push(read_row(stmt, columns.clone()));
+                    }
+                    _ => {
+                        let message = CStr::from_ptr(ffi::sqlite3_errmsg(conn))
+                            .to_string_lossy()
+                            .into_owned();
+                        return self.error(stmt, "exec error", message);
+                    }
+                }
+            }
+            ffi::sqlite3_finalize(stmt);
+
+            let rows_affected = changes(conn, total_changes);
+            Some(Ok(ResultSet::new(columns, rows, rows_affected)))
         }
     }
 }
 
+/// The rows changed by the last `INSERT`, `UPDATE` or `DELETE`, if it ran since `total_changes` was read.
+unsafe fn changes(conn: *mut ffi::sqlite3, total_changes: c_int) -> u64 {
+    if ffi::sqlite3_total_changes(conn) != total_changes {
+        ffi::sqlite3_changes(conn) as u64
+    } else {
+        0
+    }
+}
+
+/// The changes since `total_changes` and `last_insert_rowid` were read.
+unsafe fn exec_result(
+    conn: *mut ffi::sqlite3,
+    total_changes: c_int,
+    last_insert_rowid: i64,
+) -> ExecResult {
+    let last_insert_id =
+        Some(ffi::sqlite3_last_insert_rowid(conn)).filter(|&id| id != last_insert_rowid);
+    ExecResult::new(changes(conn, total_changes), last_insert_id)
+}
+
+unsafe fn columns(stmt: *mut ffi::sqlite3_stmt) -> Arc<Columns> {
+    unsafe fn text(ptr: *const c_char) -> Option<String> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+        }
+    }
+
+    let columns = (0..ffi::sqlite3_column_count(stmt))
+        .map(|i| {
+            let mut column =
+                Column::new(text(ffi::sqlite3_column_name(stmt, i)).unwrap_or_default());
+            column.decl_type = text(ffi::sqlite3_column_decltype(stmt, i));
+            column.table = text(ffi::sqlite3_column_table_name(stmt, i));
+            column.origin_name = text(ffi::sqlite3_column_origin_name(stmt, i));
+            if let Some(decl_type) = &column.decl_type {
+                let (precision, scale) = parse_precision(decl_type);
+                column.precision = precision;
+                column.scale = scale;
+            }
+            if column.origin_name.is_some() {
+                let mut not_null = 0;
+                let result = ffi::sqlite3_table_column_metadata(
+                    ffi::sqlite3_db_handle(stmt),
+                    ffi::sqlite3_column_database_name(stmt, i),
+                    ffi::sqlite3_column_table_name(stmt, i),
+                    ffi::sqlite3_column_origin_name(stmt, i),
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    &mut not_null,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                );
+                if result == ffi::SQLITE_OK {
+                    column.nullable = Some(not_null == 0);
+                }
+            }
+            column
+        })
+        .collect();
+    Arc::new(columns)
+}
+
+/// Reads the current row of `stmt`.
+///
+/// A REAL keeps the text SQLite renders for it, such as `1.0`, as its text form.
+unsafe fn read_row(stmt: *mut ffi::sqlite3_stmt, columns: Arc<Columns>) -> Row {
+    let values = (0..columns.len() as c_int)
+        .map(|i| match ffi::sqlite3_column_type(stmt, i) {
+            ffi::SQLITE_INTEGER => (Value::I64(ffi::sqlite3_column_int64(stmt, i)), None),
+            ffi::SQLITE_FLOAT => {
+                let value = Value::F64(ffi::sqlite3_column_double(stmt, i));
+                // Read after the double, as this converts the stored value to text.
+                let ptr = ffi::sqlite3_column_text(stmt, i) as *const c_char;
+                (value, Some(CStr::from_ptr(ptr).to_string_lossy().into_owned()))
+            }
+            ffi::SQLITE_TEXT => {
+                // The length is read after the text, so it counts the bytes of the text itself.
+                let ptr = ffi::sqlite3_column_text(stmt, i);
+                let count = ffi::sqlite3_column_bytes(stmt, i) as usize;
+                let text = if count == 0 || ptr.is_null() {
+                    String::new()
+                } else {
+                    let bytes = std::slice::from_raw_parts::<u8>(ptr, count);
+                    String::from_utf8_lossy(bytes).into_owned()
+                };
+                (Value::Text(Cow::Owned(text)), None)
+            }
+            ffi::SQLITE_BLOB => {
+                let ptr = ffi::sqlite3_column_blob(stmt, i);
+                let count = ffi::sqlite3_column_bytes(stmt, i) as usize;
+                // An empty blob is a null pointer.
+                let bytes = if count == 0 || ptr.is_null() {
+                    Vec::new()
+                } else {
+                    std::slice::from_raw_parts::<u8>(ptr as *const u8, count).to_vec()
+                };
+                (Value::Bytes(bytes), None)
+            }
+            _ /* ffi::SQLITE_NULL */ => (Value::Null, None),
+        })
+        .collect::<Vec<_>>();
+    Row::from_rendered(columns, values)
+}
+
+/// Binds `params`, returning the error to report if that failed.
+///
+/// The caller must return it whatever it is, as the statement is not fully bound.
 unsafe fn bind_all(
     stmt: *mut ffi::sqlite3_stmt,
     params: &[Value<'_>],
     error_level: &ErrorLevel,
-) -> Result<()> {
-    // https://sqlite.org/c3ref/c_static.html
-    const SQLITE_TRANSIENT: c_int = -1;
+) -> Option<Result<()>> {
+    let result = bind_params(stmt, params);
+    if result != ffi::SQLITE_OK {
+        return Some(Error::new(
+            error_level,
+            "bind error",
+            CStr::from_ptr(ffi::sqlite3_errstr(result)).to_string_lossy(),
+        ));
+    }
 
+    None
+}
+
+/// Binds `params` in order and returns the first error code, if any.
+unsafe fn bind_params(stmt: *mut ffi::sqlite3_stmt, params: &[Value<'_>]) -> c_int {
     for (index, param) in (1i32..).zip(params.iter()) {
         let result = match param {
             Value::Null => ffi::sqlite3_bind_null(stmt, index),
             Value::I32(value) => ffi::sqlite3_bind_int(stmt, index, *value),
             Value::I64(value) => ffi::sqlite3_bind_int64(stmt, index, *value),
+            Value::I128(value, Int128Repr::Text) => bind_text(stmt, index, &value.to_string()),
+            Value::I128(value, Int128Repr::Blob) => bind_blob(stmt, index, &value.to_be_bytes()),
+            Value::U128(value, Int128Repr::Text) => bind_text(stmt, index, &value.to_string()),
+            Value::U128(value, Int128Repr::Blob) => bind_blob(stmt, index, &value.to_be_bytes()),
             Value::F32(value) => ffi::sqlite3_bind_double(stmt, index, *value as f64),
             Value::F64(value) => ffi::sqlite3_bind_double(stmt, index, *value),
-            Value::Text(value) => ffi::sqlite3_bind_text(
-                stmt,
-                index,
-                value.as_ptr() as *const _,
-                value.len() as i32,
-                Some(std::mem::transmute::<
-                    *const c_void,
-                    extern "C" fn(*mut c_void),
-                >(SQLITE_TRANSIENT as *const c_void)),
-            ),
-            Value::Bytes(value) => ffi::sqlite3_bind_blob(
-                stmt,
-                index,
-                value.as_ptr() as *const _,
-                value.len() as i32,
-                Some(std::mem::transmute::<
-                    *const c_void,
-                    extern "C" fn(*mut c_void),
-                >(SQLITE_TRANSIENT as *const c_void)),
-            ),
-            Value::IpAddr(value) => {
-                let value = value.to_string();
-                ffi::sqlite3_bind_text(
-                    stmt,
-                    index,
-                    value.as_ptr() as *const _,
-                    value.len() as i32,
-                    Some(std::mem::transmute::<
-                        *const c_void,
-                        extern "C" fn(*mut c_void),
-                    >(SQLITE_TRANSIENT as *const c_void)),
-                )
-            }
-            Value::Time(value) => {
-                let value = value.to_string();
-                ffi::sqlite3_bind_text(
-                    stmt,
-                    index,
-                    value.as_ptr() as *const _,
-                    value.len() as i32,
-                    Some(std::mem::transmute::<
-                        *const c_void,
-                        extern "C" fn(*mut c_void),
-                    >(SQLITE_TRANSIENT as *const c_void)),
-                )
-            }
+            Value::Text(value) => bind_text(stmt, index, value),
+            Value::Bytes(value) => bind_blob(stmt, index, value),
+            Value::IpAddr(value) => bind_text(stmt, index, &value.to_string()),
+            Value::IpNetwork(value) => bind_text(stmt, index, &value.to_string()),
+            Value::MacAddr(value) => bind_text(stmt, index, &value.to_string()),
+            Value::Interval(value) => bind_text(stmt, index, &interval_to_string(value)),
+            Value::Time(value) => bind_text(stmt, index, &value.to_string()),
         };
         if result != ffi::SQLITE_OK {
-            ffi::sqlite3_finalize(stmt);
-            return Error::new(
-                error_level,
-                "bind error",
-                CStr::from_ptr(ffi::sqlite3_errstr(result)).to_string_lossy(),
-            );
+            return result;
         }
     }
 
-    Ok(())
+    ffi::SQLITE_OK
+}
+
+// https://sqlite.org/c3ref/c_static.html
+const SQLITE_TRANSIENT: c_int = -1;
+
+unsafe fn bind_text(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &str) -> c_int {
+    ffi::sqlite3_bind_text(
+        stmt,
+        index,
+        value.as_ptr() as *const _,
+        value.len() as i32,
+        Some(std::mem::transmute::<
+            *const c_void,
+            extern "C" fn(*mut c_void),
+        >(SQLITE_TRANSIENT as *const c_void)),
+    )
+}
+
+unsafe fn bind_blob(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &[u8]) -> c_int {
+    ffi::sqlite3_bind_blob(
+        stmt,
+        index,
+        value.as_ptr() as *const _,
+        value.len() as i32,
+        Some(std::mem::transmute::<
+            *const c_void,
+            extern "C" fn(*mut c_void),
+        >(SQLITE_TRANSIENT as *const c_void)),
+    )
 }
 
 #[cfg(test)]
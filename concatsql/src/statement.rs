@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+
+use crate::connection::{Connection, QueryIter};
+use crate::error::{Error, ErrorLevel};
+use crate::result_set::ExecResult;
+use crate::row::Row;
+use crate::value::{ToValue, Value};
+use crate::Result;
+
+/// A statement prepared by [`Connection::prepare`](./struct.Connection.html#method.prepare),
+/// run with a new set of parameters each time.
+///
+/// The placeholders are those of the prepared query: the `{...}` of `query!`, or the `?`/`$1`
+/// of the database in a plain string. The statement is pinned in the connection's statement
+/// cache until it is dropped, whatever the capacity, and is prepared again only after
+/// [`Connection::clear_statement_cache`](./struct.Connection.html#method.clear_statement_cache).
+/// It always runs as a single prepared statement, even without placeholders.
+///
+/// # Examples
+///
+/// ```
+/// # use concatsql::prelude::*;
+/// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+/// conn.execute("CREATE TABLE users (name TEXT, age INTEGER)").unwrap();
+///
+/// let (name, age) = ("", 0);
+/// let insert = conn.prepare(query!("INSERT INTO users VALUES ({name}, {age})")).unwrap();
+/// assert_eq!(insert.param_count(), 2);
+/// insert.execute(params!["Alice", 42]).unwrap();
+/// insert.execute(params!["Bob", 69]).unwrap();
+/// assert!(insert.execute(params!["Carol"]).is_err());
+///
+/// let select = conn.prepare("SELECT name FROM users WHERE age > ?").unwrap();
+/// let rows = select.rows(params![50]).unwrap();
+/// assert_eq!(rows[0].get("name"), Some("Bob"));
+/// ```
+#[derive(Debug)]
+pub struct Statement<'c> {
+    conn: &'c Connection,
+    pub(crate) query: String,
+    pub(crate) param_count: usize,
+}
+
+impl<'c> Statement<'c> {
+    /// Pins `query` in the statement cache of `conn` until the statement is dropped.
+    pub(crate) fn new(conn: &'c Connection, query: String) -> Self {
+        conn.conn.pin_statement(&query);
+        Statement {
+            conn,
+            query,
+            param_count: 0,
+        }
+    }
+
+    /// The number of placeholders, which every parameter set must match.
+    #[inline]
+    pub fn param_count(&self) -> usize {
+        self.param_count
+    }
+
+    /// Runs the statement without processing the resulting rows if any.
+    pub fn execute<'p>(&self, params: &[&dyn ToValue<'p>]) -> Result<()> {
+        self.execute_with_result(params).map(|_| ())
+    }
+
+    /// Runs the statement and returns the number of rows it changed and the id it generated.
+    pub fn execute_with_result<'p>(&self, params: &[&dyn ToValue<'p>]) -> Result<ExecResult> {
+        let params = match self.params(params) {
+            Ok(params) => params,
+            Err(e) => return e.map(|_| ExecResult::default()),
+        };
+        self.conn.conn.execute_inner(
+            Cow::Borrowed(&self.query),
+            &params,
+            &self.conn.error_level.get(),
+            true,
+        )
+    }
+
+    /// Runs the statement and returns the rows.
+    pub fn rows<'p>(&self, params: &[&dyn ToValue<'p>]) -> Result<Vec<Row>> {
+        let params = match self.params(params) {
+            Ok(params) => params,
+            Err(e) => return e.map(|_| Vec::new()),
+        };
+        self.conn.conn.rows_inner(
+            Cow::Borrowed(&self.query),
+            &params,
+            &self.conn.error_level.get(),
+            true,
+        )
+    }
+
+    /// Runs the statement and returns a lazy iterator over the rows.
+    pub fn query_iter<'p>(&self, params: &[&dyn ToValue<'p>]) -> QueryIter<'c> {
+        let inner = match self.params(params) {
+            Ok(params) => self.conn.conn.query_iter_inner(
+                Cow::Borrowed(&self.query),
+                &params,
+                &self.conn.error_level.get(),
+                true,
+            ),
+            Err(e) => e.map(|_| Box::new(std::iter::empty()) as Box<_>),
+        };
+        QueryIter {
+            inner: inner.unwrap_or_else(|e| Box::new(std::iter::once(Err(e)))),
+        }
+    }
+
+    /// Converts `params`, or returns the outcome of the error level if their count is wrong.
+    fn params<'p>(
+        &self,
+        params: &[&dyn ToValue<'p>],
+    ) -> std::result::Result<Vec<Value<'p>>, Result<()>> {
+        if params.len() != self.param_count {
+            return Err(param_count_error(
+                &self.conn.error_level.get(),
+                self.param_count,
+                params.len(),
+            ));
+        }
+        Ok(params.iter().map(|param| param.to_value()).collect())
+    }
+}
+
+impl Drop for Statement<'_> {
+    fn drop(&mut self) {
+        self.conn.conn.unpin_statement(&self.query);
+    }
+}
+
+/// Reports a parameter set of `got` values for a statement with `expected` placeholders.
+pub(crate) fn param_count_error(
+    error_level: &ErrorLevel,
+    expected: usize,
+    got: usize,
+) -> Result<()> {
+    Error::new(
+        error_level,
+        "bind error",
+        format!("expected {} parameters, got {}", expected, got),
+    )
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+use std::ops::Deref;
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use crate::connection::ConnKind;
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::Result;
+
+/// The isolation level of a transaction.
+///
+/// SQLite transactions are always serializable, so the level is ignored there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+/// Formats the level as SQL, e.g. `READ COMMITTED`.
+impl fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        })
+    }
+}
+
+/// Options of a transaction, created by [`Connection::build_transaction`](./struct.Connection.html#method.build_transaction).
+#[derive(Debug)]
+pub struct TransactionBuilder<'c> {
+    conn: &'c Connection,
+    isolation_level: Option<IsolationLevel>,
+    read_only: bool,
+}
+
+impl<'c> TransactionBuilder<'c> {
+    pub(crate) fn new(conn: &'c Connection) -> Self {
+        TransactionBuilder {
+            conn,
+            isolation_level: None,
+            read_only: false,
+        }
+    }
+
+    /// Sets the isolation level, instead of the default of the database.
+    pub fn isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.isolation_level = Some(isolation_level);
+        self
+    }
+
+    /// Rejects writes for the duration of the transaction.
+    ///
+    /// SQLite has no read-only transactions, so this sets `PRAGMA query_only` until the transaction ends.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Begins the transaction.
+    ///
+    /// Fails if the connection is already in a transaction started by this crate;
+    /// use [`Transaction::savepoint`] to nest them.
+    #[cfg_attr(
+        not(any(feature = "sqlite", feature = "mysql", feature = "postgres")),
+        allow(unreachable_code)
+    )]
+    pub fn start(self) -> Result<Transaction<'c>> {
+        let conn = self.conn;
+        if conn.in_transaction.replace(true) {
+            return Err(Error::Message(
+                "a transaction is already in progress".to_string(),
+            ));
+        }
+
+        // SQLite has no read-only transactions, so `PRAGMA query_only` is set instead.
+        let (begin, query_only): (String, bool) = match conn.conn.kind() {
+            #[cfg(feature = "sqlite")]
+            ConnKind::SQLite => ("BEGIN".to_string(), self.read_only),
+            #[cfg(feature = "mysql")]
+            ConnKind::MySQL => {
+                // MySQL applies `SET TRANSACTION` to the next transaction only.
+                if let Some(level) = self.isolation_level {
+                    if let Err(e) =
+                        conn.execute_raw(format!("SET TRANSACTION ISOLATION LEVEL {}", level))
+                    {
+                        conn.in_transaction.set(false);
+                        return Err(e);
+                    }
+                }
+                let mode = if self.read_only { " READ ONLY" } else { "" };
+                (format!("START TRANSACTION{}", mode), false)
+            }
+            #[cfg(feature = "postgres")]
+            ConnKind::PostgreSQL => {
+                let mut begin = String::from("BEGIN");
+                if let Some(level) = self.isolation_level {
+                    begin += &format!(" ISOLATION LEVEL {}", level);
+                }
+                if self.read_only {
+                    begin += " READ ONLY";
+                }
+                (begin, false)
+            }
+        };
+        if let Err(e) = conn.execute_raw(begin) {
+            conn.in_transaction.set(false);
+            return Err(e);
+        }
+
+        let transaction = Transaction {
+            conn,
+            depth: 0,
+            query_only,
+            done: false,
+        };
+        if query_only {
+            // Dropping the transaction on failure rolls it back.
+            conn.execute_raw("PRAGMA query_only = ON")?;
+        }
+        Ok(transaction)
+    }
+}
+
+/// A transaction, or a savepoint inside one.
+///
+/// It dereferences to the [`Connection`], so every query method is available on it.
+/// The transaction is rolled back when dropped without [`commit`](#method.commit),
+/// including on an early return with `?`.
+///
+/// # Examples
+///
+/// ```
+/// # use concatsql::prelude::*;
+/// # let conn = concatsql::sqlite::open(":memory:").unwrap();
+/// conn.execute("CREATE TABLE users (name TEXT)").unwrap();
+///
+/// let mut tx = conn.transaction().unwrap();
+/// tx.execute("INSERT INTO users VALUES ('Alice')").unwrap();
+/// {
+///     let sp = tx.savepoint().unwrap();
+///     sp.execute("INSERT INTO users VALUES ('Bob')").unwrap();
+///     // Dropped without `commit`: only Bob is rolled back.
+/// }
+/// tx.commit().unwrap();
+///
+/// assert_eq!(conn.query_scalar::<i64>("SELECT count(*) FROM users"), Ok(1));
+/// ```
+#[derive(Debug)]
+pub struct Transaction<'c> {
+    conn: &'c Connection,
+    /// 0 for the transaction itself, 1 and above for savepoints.
+    depth: u32,
+    /// Whether `PRAGMA query_only` has to be reset at the end (SQLite).
+    query_only: bool,
+    done: bool,
+}
+
+impl<'c> Transaction<'c> {
+    /// Starts a savepoint, which can be committed or rolled back on its own.
+    ///
+    /// The transaction cannot be used until the savepoint ends.
+    pub fn savepoint(&mut self) -> Result<Transaction<'_>> {
+        let depth = self.depth + 1;
+        self.conn
+            .execute_raw(format!("SAVEPOINT {}", savepoint_name(depth)))?;
+        Ok(Transaction {
+            conn: self.conn,
+            depth,
+            query_only: false,
+            done: false,
+        })
+    }
+
+    /// Commits the transaction, or releases the savepoint.
+    ///
+    /// If this fails, the transaction is rolled back.
+    pub fn commit(mut self) -> Result<()> {
+        self.finish(true)
+    }
+
+    /// Rolls back the transaction or savepoint, as dropping it does, but reports errors.
+    pub fn rollback(mut self) -> Result<()> {
+        self.finish(false)
+    }
+
+    fn finish(&mut self, commit: bool) -> Result<()> {
+        if self.depth == 0 {
+            self.conn
+                .execute_raw(if commit { "COMMIT" } else { "ROLLBACK" })?;
+            self.done = true;
+            self.conn.in_transaction.set(false);
+            if self.query_only {
+                self.conn.execute_raw("PRAGMA query_only = OFF")?;
+            }
+        } else {
+            let name = savepoint_name(self.depth);
+            if !commit {
+                self.conn
+                    .execute_raw(format!("ROLLBACK TO SAVEPOINT {}", name))?;
+            }
+            self.conn
+                .execute_raw(format!("RELEASE SAVEPOINT {}", name))?;
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = Connection;
+
+    #[inline]
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.finish(false);
+        }
+        if self.depth == 0 {
+            self.conn.in_transaction.set(false);
+            if self.query_only && !self.done {
+                let _ = self.conn.execute_raw("PRAGMA query_only = OFF");
+            }
+        }
+    }
+}
+
+fn savepoint_name(depth: u32) -> String {
+    format!("concatsql_savepoint_{}", depth)
+}
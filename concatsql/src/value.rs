@@ -1,6 +1,11 @@
 use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::net::IpAddr;
 use std::time::SystemTime;
+use uuid::Uuid;
+
+use crate::net::{IpNetwork, MacAddr};
+use crate::parser::to_hex;
 
 use chrono::offset::Utc;
 use chrono::DateTime;
@@ -11,17 +16,90 @@ pub enum Value<'a> {
     Null,
     I32(i32),
     I64(i64),
+    I128(i128, Int128Repr),
+    U128(u128, Int128Repr),
     F32(f32),
     F64(f64),
     Text(Cow<'a, str>),
     Bytes(Vec<u8>),
     IpAddr(IpAddr),
+    IpNetwork(IpNetwork),
+    MacAddr(MacAddr),
     Time(SystemTime),
+    Interval(chrono::Duration),
+}
+
+impl Value<'_> {
+    /// Returns the text form of a column value, or `None` for `NULL`.
+    ///
+    /// Blobs are hex-encoded.
+    pub(crate) fn to_text(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Owned(match self {
+            Value::Null => return None,
+            Value::Text(value) => return Some(Cow::Borrowed(value)),
+            Value::I32(value) => value.to_string(),
+            Value::I64(value) => value.to_string(),
+            Value::I128(value, _) => value.to_string(),
+            Value::U128(value, _) => value.to_string(),
+            Value::F32(value) => value.to_string(),
+            Value::F64(value) => value.to_string(),
+            Value::Bytes(value) => to_hex(value),
+            Value::IpAddr(value) => value.to_string(),
+            Value::IpNetwork(value) => value.to_string(),
+            Value::MacAddr(value) => value.to_string(),
+            Value::Time(value) => SystemTimeToString::to_string(value),
+            Value::Interval(value) => interval_to_string(value),
+        }))
+    }
+
+    /// Copies borrowed text so the value outlives the query it was bound to.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::Null => Value::Null,
+            Value::I32(value) => Value::I32(value),
+            Value::I64(value) => Value::I64(value),
+            Value::I128(value, repr) => Value::I128(value, repr),
+            Value::U128(value, repr) => Value::U128(value, repr),
+            Value::F32(value) => Value::F32(value),
+            Value::F64(value) => Value::F64(value),
+            Value::Text(value) => Value::Text(Cow::Owned(value.into_owned())),
+            Value::Bytes(value) => Value::Bytes(value),
+            Value::IpAddr(value) => Value::IpAddr(value),
+            Value::IpNetwork(value) => Value::IpNetwork(value),
+            Value::MacAddr(value) => Value::MacAddr(value),
+            Value::Time(value) => Value::Time(value),
+            Value::Interval(value) => Value::Interval(value),
+        }
+    }
+}
+
+/// How a 128-bit integer is bound, as no database has an integer type that wide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Int128Repr {
+    /// Decimal digits: `NUMERIC`/`DECIMAL` on PostgreSQL and MySQL, text on SQLite.
+    Text,
+    /// 16-byte big-endian two's complement blob (`bytea` on PostgreSQL).
+    Blob,
 }
 
 /// A trait for types that can be converted into Database values.
+///
+/// Any type implementing `ToValue` can be concatenated to a [`WrapString`](./struct.WrapString.html)
+/// and used in `query!` and `params!`.
 pub trait ToValue<'a> {
     fn to_value(&self) -> Value<'a>;
+
+    /// Converts an owned value.
+    ///
+    /// Types holding a buffer override this to move it instead of copying.
+    #[inline]
+    fn into_value(self) -> Value<'a>
+    where
+        Self: Sized,
+    {
+        self.to_value()
+    }
 }
 
 impl<'a> ToValue<'a> for () {
@@ -30,6 +108,22 @@ impl<'a> ToValue<'a> for () {
     }
 }
 
+impl<'a, T: ToValue<'a>> ToValue<'a> for Option<T> {
+    fn to_value(&self) -> Value<'a> {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::Null,
+        }
+    }
+
+    fn into_value(self) -> Value<'a> {
+        match self {
+            Some(value) => value.into_value(),
+            None => Value::Null,
+        }
+    }
+}
+
 macro_rules! impl_to_value_for_i32 {
     ( $($t:ty),* ) => {$(
         impl<'a> ToValue<'a> for $t {
@@ -41,13 +135,93 @@ macro_rules! impl_to_value_for_i32 {
     ( $($t:ty,)* ) => { impl_to_value_for_i32!{ $( $t ),* } }
 }
 
+macro_rules! impl_to_value_for_i64 {
+    ( $($t:ty),* ) => {$(
+        impl<'a> ToValue<'a> for $t {
+            fn to_value(&self) -> Value<'a> {
+                Value::I64(*self as i64)
+            }
+        }
+    )*};
+    ( $($t:ty,)* ) => { impl_to_value_for_i64!{ $( $t ),* } }
+}
+
 impl_to_value_for_i32! {
+    u8, u16,
     i8, i16, i32,
 }
 
-impl<'a> ToValue<'a> for i64 {
+impl_to_value_for_i64! {
+    u32, i64,
+}
+
+/// Binds as a 64-bit integer when it fits, and as a 128-bit one otherwise.
+impl<'a> ToValue<'a> for u64 {
+    fn to_value(&self) -> Value<'a> {
+        match i64::try_from(*self) {
+            Ok(value) => Value::I64(value),
+            Err(_) => Value::U128(u128::from(*self), Int128Repr::Text),
+        }
+    }
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+impl_to_value_for_i32! {
+    isize,
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+impl_to_value_for_i64! {
+    usize,
+}
+
+#[cfg(target_pointer_width = "64")]
+impl_to_value_for_i64! {
+    isize,
+}
+
+#[cfg(target_pointer_width = "64")]
+impl<'a> ToValue<'a> for usize {
+    fn to_value(&self) -> Value<'a> {
+        (*self as u64).to_value()
+    }
+}
+
+impl<'a> ToValue<'a> for i128 {
+    fn to_value(&self) -> Value<'a> {
+        Value::I128(*self, Int128Repr::Text)
+    }
+}
+
+impl<'a> ToValue<'a> for u128 {
+    fn to_value(&self) -> Value<'a> {
+        Value::U128(*self, Int128Repr::Text)
+    }
+}
+
+/// Binds a 128-bit integer as a 16-byte big-endian blob instead of a decimal number.
+///
+/// `FromSql` for `i128` and `u128` reads such a blob back.
+///
+/// ```
+/// # use concatsql::prelude::*;
+/// use concatsql::AsBlob;
+///
+/// let id = u128::MAX;
+/// let sql = query!("SELECT {id}, ") + AsBlob(id);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AsBlob<T>(pub T);
+
+impl<'a> ToValue<'a> for AsBlob<i128> {
+    fn to_value(&self) -> Value<'a> {
+        Value::I128(self.0, Int128Repr::Blob)
+    }
+}
+
+impl<'a> ToValue<'a> for AsBlob<u128> {
     fn to_value(&self) -> Value<'a> {
-        Value::I64(*self)
+        Value::U128(self.0, Int128Repr::Blob)
     }
 }
 
@@ -67,6 +241,16 @@ impl<'a> ToValue<'a> for String {
     fn to_value(&self) -> Value<'a> {
         Value::Text(Cow::Owned(self.to_string()))
     }
+
+    fn into_value(self) -> Value<'a> {
+        Value::Text(Cow::Owned(self))
+    }
+}
+
+impl<'a> ToValue<'a> for &'a String {
+    fn to_value(&self) -> Value<'a> {
+        Value::Text(Cow::Borrowed(self))
+    }
 }
 
 impl<'a> ToValue<'a> for &'a str {
@@ -75,27 +259,60 @@ impl<'a> ToValue<'a> for &'a str {
     }
 }
 
+impl<'a> ToValue<'a> for &'a &str {
+    fn to_value(&self) -> Value<'a> {
+        Value::Text(Cow::Borrowed(self))
+    }
+}
+
+impl<'a> ToValue<'a> for Cow<'a, str> {
+    fn to_value(&self) -> Value<'a> {
+        Value::Text(self.clone())
+    }
+
+    fn into_value(self) -> Value<'a> {
+        Value::Text(self)
+    }
+}
+
+impl<'a> ToValue<'a> for &'a Cow<'a, str> {
+    fn to_value(&self) -> Value<'a> {
+        Value::Text(Cow::Borrowed(self))
+    }
+}
+
 impl<'a> ToValue<'a> for Vec<u8> {
     fn to_value(&self) -> Value<'a> {
         Value::Bytes(self.clone())
     }
+
+    fn into_value(self) -> Value<'a> {
+        Value::Bytes(self)
+    }
 }
 
-impl<'a> ToValue<'a> for &'a Vec<u8> {
+impl<'a> ToValue<'a> for &Vec<u8> {
     fn to_value(&self) -> Value<'a> {
         Value::Bytes(self.to_vec())
     }
 }
 
-impl<'a> ToValue<'a> for IpAddr {
+impl<'a> ToValue<'a> for &[u8] {
     fn to_value(&self) -> Value<'a> {
-        Value::IpAddr(*self)
+        Value::Bytes(self.to_vec())
     }
 }
 
-impl<'a> ToValue<'a> for &'a IpAddr {
+/// Sent as a 32-byte string.
+impl<'a> ToValue<'a> for Uuid {
     fn to_value(&self) -> Value<'a> {
-        Value::IpAddr(**self)
+        Value::Text(Cow::Owned(format!("{:X}", self.simple())))
+    }
+}
+
+impl<'a> ToValue<'a> for IpAddr {
+    fn to_value(&self) -> Value<'a> {
+        Value::IpAddr(*self)
     }
 }
 
@@ -105,12 +322,52 @@ impl<'a> ToValue<'a> for SystemTime {
     }
 }
 
-impl<'a> ToValue<'a> for &'a SystemTime {
+impl<'a> ToValue<'a> for chrono::Duration {
+    fn to_value(&self) -> Value<'a> {
+        Value::Interval(*self)
+    }
+}
+
+/// Durations longer than [`chrono::Duration::MAX`] are saturated.
+impl<'a> ToValue<'a> for std::time::Duration {
+    fn to_value(&self) -> Value<'a> {
+        Value::Interval(chrono::Duration::from_std(*self).unwrap_or(chrono::Duration::MAX))
+    }
+}
+
+impl<'a> ToValue<'a> for IpNetwork {
+    fn to_value(&self) -> Value<'a> {
+        Value::IpNetwork(*self)
+    }
+}
+
+impl<'a> ToValue<'a> for MacAddr {
     fn to_value(&self) -> Value<'a> {
-        Value::Time(**self)
+        Value::MacAddr(*self)
     }
 }
 
+macro_rules! impl_to_value_for_ref {
+    ( $($t:ty),* ) => {$(
+        impl<'a> ToValue<'a> for &$t {
+            fn to_value(&self) -> Value<'a> {
+                (**self).to_value()
+            }
+        }
+    )*};
+    ( $($t:ty,)* ) => { impl_to_value_for_ref!{ $( $t ),* } }
+}
+
+impl_to_value_for_ref! {
+    Uuid,
+    IpAddr,
+    IpNetwork,
+    MacAddr,
+    SystemTime,
+    chrono::Duration,
+    std::time::Duration,
+}
+
 pub trait SystemTimeToString {
     fn to_string(&self) -> String;
 }
@@ -128,3 +385,21 @@ impl SystemTimeToString for &SystemTime {
         datetime.format("%Y-%m-%d %H:%M:%S.%f").to_string()
     }
 }
+
+/// Formats an interval as `[-]hh:mm:ss.ffffff`, where the hours may exceed 24.
+pub(crate) fn interval_to_string(interval: &chrono::Duration) -> String {
+    let abs = interval.abs();
+    let secs = abs.num_seconds();
+    format!(
+        "{}{:02}:{:02}:{:02}.{:06}",
+        if *interval < chrono::Duration::zero() {
+            "-"
+        } else {
+            ""
+        },
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        abs.subsec_nanos() / 1000
+    )
+}
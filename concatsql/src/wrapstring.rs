@@ -1,12 +1,9 @@
 use std::borrow::Cow;
-use std::net::IpAddr;
 use std::ops::Add;
-use std::time::SystemTime;
-use uuid::Uuid;
 
 use crate::connection::ConnKind;
 use crate::parser::{escape_string, to_binary_literal};
-use crate::value::{SystemTimeToString, ToValue, Value};
+use crate::value::{interval_to_string, Int128Repr, SystemTimeToString, ToValue, Value};
 
 /// Wraps a [String](https://doc.rust-lang.org/std/string/struct.String.html) type.
 #[derive(Clone, Debug, PartialEq)]
@@ -79,12 +76,25 @@ impl<'a> WrapString<'a> {
                         Value::Null => query.push_str("NULL"),
                         Value::I32(value) => query.push_str(&value.to_string()),
                         Value::I64(value) => query.push_str(&value.to_string()),
+                        Value::I128(value, Int128Repr::Text) => query.push_str(&value.to_string()),
+                        Value::I128(value, Int128Repr::Blob) => {
+                            query.push_str(&to_binary_literal(&value.to_be_bytes()))
+                        }
+                        Value::U128(value, Int128Repr::Text) => query.push_str(&value.to_string()),
+                        Value::U128(value, Int128Repr::Blob) => {
+                            query.push_str(&to_binary_literal(&value.to_be_bytes()))
+                        }
                         Value::F32(value) => query.push_str(&value.to_string()),
                         Value::F64(value) => query.push_str(&value.to_string()),
                         Value::Text(value) => query.push_str(&escape_string(value)),
                         Value::Bytes(value) => query.push_str(&to_binary_literal(value)),
                         Value::IpAddr(value) => query.push_str(&format!("'{}'", value)),
+                        Value::IpNetwork(value) => query.push_str(&format!("'{}'", value)),
+                        Value::MacAddr(value) => query.push_str(&format!("'{}'", value)),
                         Value::Time(value) => query.push_str(&format!("'{}'", value.to_string())),
+                        Value::Interval(value) => {
+                            query.push_str(&format!("'{}'", interval_to_string(value)))
+                        }
                     }
                     index += 1;
                 }
@@ -174,274 +184,29 @@ impl<'a, 'b> Add<&'b WrapString<'a>> for WrapString<'a> {
     }
 }
 
-impl<'a> Add<String> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: String) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Text(Cow::Owned(other)));
-        self
-    }
-}
-
-impl<'a> Add<&'a String> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &'a String) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Text(Cow::Borrowed(other)));
-        self
-    }
-}
-
-impl<'a> Add<&'a str> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &'a str) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Text(Cow::Borrowed(other)));
-        self
-    }
-}
-
-impl<'a> Add<&'a &str> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &'a &str) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Text(Cow::Borrowed(other)));
-        self
-    }
-}
-
-impl<'a> Add<std::borrow::Cow<'a, str>> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: std::borrow::Cow<'a, str>) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Text(other));
-        self
-    }
-}
-
-impl<'a> Add<&'a std::borrow::Cow<'a, str>> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &'a std::borrow::Cow<'a, str>) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Text(Cow::Borrowed(other)));
-        self
-    }
-}
-
-impl<'a> Add<Vec<u8>> for WrapString<'a> {
+impl<'a, T: ToValue<'a>> Add<T> for WrapString<'a> {
     type Output = WrapString<'a>;
     #[inline]
-    fn add(mut self, other: Vec<u8>) -> WrapString<'a> {
+    fn add(mut self, other: T) -> WrapString<'a> {
         self.query.push(None);
-        self.params.push(Value::Bytes(other));
+        self.params.push(other.into_value());
         self
     }
 }
 
-impl<'a> Add<&Vec<u8>> for WrapString<'a> {
+impl<'a> Add<&[&dyn ToValue<'a>]> for WrapString<'a> {
     type Output = WrapString<'a>;
     #[inline]
-    fn add(mut self, other: &Vec<u8>) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Bytes(other.clone()));
-        self
-    }
-}
-
-impl<'a> Add<&[u8]> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &[u8]) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Bytes(other.to_vec()));
-        self
-    }
-}
-
-impl<'a> Add<&[&(dyn ToValue<'a>)]> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &[&(dyn ToValue<'a>)]) -> WrapString<'a> {
+    fn add(mut self, other: &[&dyn ToValue<'a>]) -> WrapString<'a> {
         if let Some(first) = other.first() {
             self.query.push(None);
-            self.params.push(first.to_value());
+            self.params.push((**first).to_value());
         }
         for param in other.iter().skip(1) {
             self.query.push(Some(Cow::Borrowed(",")));
             self.query.push(None);
-            self.params.push(param.to_value());
-        }
-        self
-    }
-}
-
-macro_rules! impl_add_I32_for_WrapString {
-    ( $($t:ty),* ) => ($(
-        impl<'a> Add<$t> for WrapString<'a> {
-            type Output = WrapString<'a>;
-            #[inline]
-            fn add(mut self, other: $t) -> WrapString<'a> {
-                self.query .push(None);
-                self.params.push(Value::I32(other as i32));
-                self
-            }
-        }
-    )*)
-}
-
-macro_rules! impl_add_I64_for_WrapString {
-    ( $($t:ty),* ) => ($(
-        impl<'a> Add<$t> for WrapString<'a> {
-            type Output = WrapString<'a>;
-            #[inline]
-            fn add(mut self, other: $t) -> WrapString<'a> {
-                self.query .push(None);
-                self.params.push(Value::I64(other as i64));
-                self
-            }
+            self.params.push((**param).to_value());
         }
-    )*)
-}
-
-/// Sent as a 32-byte string.
-impl<'a> Add<Uuid> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: Uuid) -> WrapString<'a> {
-        self.query.push(None);
-        self.params
-            .push(Value::Text(Cow::Owned(format!("{:X}", other.simple()))));
-        self
-    }
-}
-
-/// Sent as a 32-byte string.
-impl<'a> Add<&Uuid> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &Uuid) -> WrapString<'a> {
-        self.query.push(None);
-        self.params
-            .push(Value::Text(Cow::Owned(format!("{:X}", other.simple()))));
-        self
-    }
-}
-
-impl<'a> Add<IpAddr> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: IpAddr) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::IpAddr(other));
-        self
-    }
-}
-
-impl<'a> Add<&IpAddr> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &IpAddr) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::IpAddr(*other));
-        self
-    }
-}
-
-impl<'a> Add<SystemTime> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: SystemTime) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Time(other));
-        self
-    }
-}
-
-impl<'a> Add<&SystemTime> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: &SystemTime) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Time(*other));
-        self
-    }
-}
-
-impl_add_I32_for_WrapString!(u8, u16, u32, i8, i16, i32);
-impl_add_I64_for_WrapString!(u64, i64);
-
-#[cfg(target_pointer_width = "16")]
-#[cfg(target_pointer_width = "32")]
-impl_add_I32_for_WrapString!(usize, isize);
-
-#[cfg(target_pointer_width = "64")]
-impl_add_I64_for_WrapString!(usize, isize);
-
-impl<'a> Add<f32> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: f32) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::F32(other));
-        self
-    }
-}
-
-impl<'a> Add<f64> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, other: f64) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::F64(other));
-        self
-    }
-}
-
-macro_rules! impl_add_Option_for_WrapString {
-    ( $($t:ty),* ) => {$(
-        impl<'a> Add<Option<$t>> for WrapString<'a> {
-            type Output = WrapString<'a>;
-            #[inline]
-            fn add(mut self, other: Option<$t>) -> WrapString<'a> {
-                match other {
-                    Some(other) => self.add(other),
-                    None => {
-                        self.query .push(None);
-                        self.params.push(Value::Null);
-                        self
-                    }
-                }
-            }
-        }
-    )*};
-    ( $($t:ty,)* ) => { impl_add_Option_for_WrapString!{ $( $t ),* } }
-}
-
-impl_add_Option_for_WrapString! {
-    String,
-    &'a String,
-    &'a str,
-    std::borrow::Cow<'a, str>,
-    Vec<u8>,
-    &'a Vec<u8>,
-    u8, u16, u32, u64, usize,
-    i8, i16, i32, i64, isize,
-    f32, f64,
-    Uuid,
-}
-
-impl<'a> Add<()> for WrapString<'a> {
-    type Output = WrapString<'a>;
-    #[inline]
-    fn add(mut self, _other: ()) -> WrapString<'a> {
-        self.query.push(None);
-        self.params.push(Value::Null);
         self
     }
 }
@@ -611,7 +376,7 @@ impl<'a> IntoWrapString<'a> for WrapString<'a> {
     }
 }
 
-impl<'a, 'b> IntoWrapString<'a> for &'b WrapString<'a> {
+impl<'a> IntoWrapString<'a> for &WrapString<'a> {
     #[doc(hidden)]
     #[inline]
     fn compile(&self, kind: ConnKind) -> Cow<'a, str> {
@@ -729,8 +494,23 @@ mod tests {
         assert_eq!(sql.simulate(), "'127.0.0.1'");
         let sql = query!("") + IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
         assert_eq!(sql.simulate(), "'::1'");
+        let sql = query!("") + "10.0.0.0/8".parse::<crate::IpNetwork>().unwrap();
+        assert_eq!(sql.simulate(), "'10.0.0.0/8'");
+        let sql = query!("") + crate::MacAddr([0x08, 0, 0x2b, 1, 2, 3]);
+        assert_eq!(sql.simulate(), "'08:00:2b:01:02:03'");
         let sql = query!("") + UNIX_EPOCH;
         assert_eq!(sql.simulate(), "'1970-01-01 00:00:00.000000000'");
+        let sql = query!("") + i128::MIN + query!(",") + u128::MAX;
+        assert_eq!(
+            sql.simulate(),
+            "-170141183460469231731687303715884105728,340282366920938463463374607431768211455"
+        );
+        let sql = query!("") + crate::AsBlob(-1i128);
+        assert_eq!(sql.simulate(), super::to_binary_literal(&[0xff; 16]));
+        let sql = query!("") + std::time::Duration::from_millis(90_500);
+        assert_eq!(sql.simulate(), "'00:01:30.500000'");
+        let sql = query!("") + chrono::Duration::hours(-100);
+        assert_eq!(sql.simulate(), "'-100:00:00.000000'");
     }
 
     #[test]
@@ -754,6 +534,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unsigned_bounds() {
+        use concatsql::Int128Repr;
+        assert_eq!(u32::MAX.to_value(), Value::I64(4294967295));
+        assert_eq!(
+            (i64::MAX as u64).to_value(),
+            Value::I64(9223372036854775807)
+        );
+        assert_eq!(
+            (i64::MAX as u64 + 1).to_value(),
+            Value::U128(9223372036854775808, Int128Repr::Text)
+        );
+        assert_eq!(
+            u64::MAX.to_value(),
+            Value::U128(18446744073709551615, Int128Repr::Text)
+        );
+        assert_eq!(usize::MAX.to_value(), (usize::MAX as u64).to_value());
+        let sql = query!("") + u32::MAX + query!(",") + u64::MAX;
+        assert_eq!(sql.simulate(), "4294967295,18446744073709551615");
+    }
+
+    #[test]
+    fn custom_to_value() {
+        struct Celsius(f64);
+        impl<'a> ToValue<'a> for Celsius {
+            fn to_value(&self) -> Value<'a> {
+                Value::F64(self.0)
+            }
+        }
+
+        let temp = Celsius(36.5);
+        let sql = query!("{temp}") + Celsius(-1.5) + Some(Celsius(0.25)) + None::<Celsius>;
+        assert_eq!(sql.simulate(), "36.5-1.50.25NULL");
+        let sql = query!("")
+            + Some(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)))
+            + None::<IpAddr>
+            + Some(UNIX_EPOCH)
+            + None::<std::time::SystemTime>;
+        assert_eq!(
+            sql.simulate(),
+            "'127.0.0.1'NULL'1970-01-01 00:00:00.000000000'NULL"
+        );
+    }
+
     #[test]
     #[allow(clippy::op_ref)]
     fn uuid() {
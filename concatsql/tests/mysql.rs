@@ -4,7 +4,7 @@
 #[cfg(debug_assertions)]
 mod mysql {
     use concatsql::prelude::*;
-    use concatsql::{Error, ErrorLevel};
+    use concatsql::{Error, ErrorLevel, ExecResult, IpNetwork, IsolationLevel, MacAddr};
 
     macro_rules! err {
         () => {
@@ -50,13 +50,16 @@ mod mysql {
         let expects = ["Alice", "Bob", "Carol"];
         let sql = query!("SELECT name FROM users;");
 
+        let mut i = 0;
         conn.iterate(sql, |pairs| {
-            for (i, (_, value)) in pairs.iter().enumerate() {
-                assert_eq!(*value.as_ref().unwrap(), expects[i]);
+            for &(_, value) in pairs.iter() {
+                assert_eq!(value.unwrap(), expects[i]);
             }
+            i += 1;
             true
         })
         .unwrap();
+        assert_eq!(i, expects.len());
     }
 
     #[test]
@@ -65,13 +68,16 @@ mod mysql {
         let expects = ["Alice", "Bob", "Carol", "Alice", "Bob", "Carol"];
         let sql = query!("SELECT name FROM users; SELECT name FROM users;");
 
+        let mut i = 0;
         conn.iterate(sql, |pairs| {
-            for (i, (_, value)) in pairs.iter().enumerate() {
-                assert_eq!(*value.as_ref().unwrap(), expects[i]);
+            for &(_, value) in pairs.iter() {
+                assert_eq!(value.unwrap(), expects[i]);
             }
+            i += 1;
             true
         })
         .unwrap();
+        assert_eq!(i, expects.len());
     }
 
     #[test]
@@ -86,13 +92,16 @@ mod mysql {
             + age
             + &query!(" < age");
 
+        let mut i = 0;
         conn.iterate(sql, |pairs| {
-            for (i, (_, value)) in pairs.iter().enumerate() {
-                assert_eq!(*value.as_ref().unwrap(), expects[i]);
+            for &(_, value) in pairs.iter() {
+                assert_eq!(value.unwrap(), expects[i]);
             }
+            i += 1;
             true
         })
         .unwrap();
+        assert_eq!(i, expects.len());
     }
 
     #[test]
@@ -397,6 +406,554 @@ mod mysql {
         }
     }
 
+    #[test]
+    fn null() {
+        let conn = prepare();
+        let name: Option<&str> = None;
+        let age: Option<i32> = None;
+        let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+        conn.execute(&sql).unwrap();
+        let sql = query!("INSERT INTO users VALUES ('Dave', ") + age + query!(")");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn
+            .rows("SELECT name, age FROM users WHERE age IS NULL ORDER BY name")
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), None);
+        assert_eq!(rows[0].get("age"), None);
+        assert_eq!(rows[1].get("name"), Some("Dave"));
+        assert_eq!(rows[1].get("age"), None);
+        assert_eq!(rows[1].get_into::<_, Option<i32>>("age"), Ok(None));
+        assert_eq!(
+            rows[1].get_into::<_, i32>("age"),
+            Err(Error::UnexpectedNull("age".to_string()))
+        );
+    }
+
+    #[test]
+    fn interval() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE slas (name TEXT, window TIME(6))")
+            .unwrap();
+        let window = std::time::Duration::from_secs(90 * 60);
+        let sql = query!("INSERT INTO slas VALUES ('std', {window})");
+        conn.execute(&sql).unwrap();
+        let window = chrono::Duration::hours(-26) + chrono::Duration::microseconds(5);
+        let sql = query!("INSERT INTO slas VALUES ('chrono', {window})");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM slas ORDER BY name").unwrap();
+        assert_eq!(rows[0].get("window"), Some("-025:59:59.999995"));
+        assert_eq!(
+            rows[0].get_into::<_, chrono::Duration>("window"),
+            Ok(chrono::Duration::hours(-26) + chrono::Duration::microseconds(5))
+        );
+        assert!(rows[0]
+            .get_into::<_, std::time::Duration>("window")
+            .is_err());
+        assert_eq!(rows[1].get("window"), Some("001:30:00.000000"));
+        assert_eq!(
+            rows[1].get_into::<_, std::time::Duration>("window"),
+            Ok(std::time::Duration::from_secs(90 * 60))
+        );
+    }
+
+    #[test]
+    fn int128() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE ids (id DECIMAL(39, 0))")
+            .unwrap();
+        let min = i128::MIN;
+        let max = u128::MAX;
+        let sql = query!("INSERT INTO ids VALUES ({min}), ({max}), (0)");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT id FROM ids ORDER BY id").unwrap();
+        assert_eq!(rows[0].get_into::<_, i128>("id"), Ok(i128::MIN));
+        assert_eq!(rows[1].get("id"), Some("0"));
+        assert_eq!(rows[2].get_into::<_, u128>("id"), Ok(u128::MAX));
+
+        let sql = query!("SELECT ") + concatsql::AsBlob(-2i128) + query!(" AS id");
+        let mut bytes = vec![0xff; 16];
+        bytes[15] = 0xfe;
+        let rows = conn.rows(&sql).unwrap();
+        assert_eq!(rows[0].get_value("id"), Some(&Value::Bytes(bytes)));
+    }
+
+    #[test]
+    fn network() {
+        let conn = prepare();
+        let net: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let mac = MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]);
+        let sql = query!("SELECT {net} AS net, {mac} AS mac");
+        let row = &conn.rows(&sql).unwrap()[0];
+        assert_eq!(row.get("net"), Some("2001:db8::/32"));
+        assert_eq!(row.get("mac"), Some("08:00:2b:01:02:03"));
+        assert_eq!(row.get_into::<_, IpNetwork>("net"), Ok(net));
+        assert_eq!(row.get_into::<_, MacAddr>("mac"), Ok(mac));
+    }
+
+    #[test]
+    fn get_bytes() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE files (data VARBINARY(16), name TEXT)")
+            .unwrap();
+        let data = b"ABCD".to_vec();
+        let sql = query!("INSERT INTO files VALUES ({data}, 'ABCD')");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM files").unwrap();
+        assert_eq!(rows[0].get_bytes("data"), Some(&b"ABCD"[..]));
+        assert_eq!(rows[0].get_value("data"), Some(&Value::Bytes(data.clone())));
+        assert_eq!(rows[0].get_into::<_, Vec<u8>>("data"), Ok(data));
+        assert_eq!(rows[0].get("data"), Some("41424344"));
+        assert_eq!(rows[0].get_bytes("name"), Some(&b"ABCD"[..]));
+        assert_eq!(rows[0].get_into::<_, Vec<u8>>("name"), Ok(b"ABCD".to_vec()));
+    }
+
+    #[test]
+    fn query_iter() {
+        let conn = prepare();
+        let mut iter = conn.query_iter("SELECT name, age FROM users ORDER BY age");
+        let row = iter.next().unwrap().unwrap();
+        assert_eq!(row.get("name"), Some("Alice"));
+        assert_eq!(row.get_into::<_, i64>("age"), Ok(42));
+        // The iterator holds the connection until it is dropped.
+        assert_eq!(
+            conn.rows("SELECT 1"),
+            err!("connection is busy with an unfinished result")
+        );
+        drop(iter);
+
+        // The connection is usable again once the iterator is dropped.
+        conn.execute("INSERT INTO users VALUES ('Dave', 7)")
+            .unwrap();
+        let mut names = conn
+            .query_iter(query!("SELECT name FROM users WHERE age < ") + 50)
+            .map(|row| row.unwrap().get_into::<_, String>(0).unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["Alice", "Dave"]);
+
+        // And as soon as the rows run out.
+        let mut iter = conn.query_iter("SELECT name FROM users");
+        assert_eq!(iter.by_ref().count(), 4);
+        assert!(conn.rows("SELECT 1").is_ok());
+        drop(iter);
+
+        // The columns are known before the first row, and without any.
+        let mut iter = conn.query_iter("SELECT name, age FROM users ORDER BY age");
+        assert_eq!(iter.columns()[0].name(), "name");
+        assert_eq!(iter.next().unwrap().unwrap().get(0), Some("Dave"));
+        drop(iter);
+        let mut iter = conn.query_iter("SELECT name, age FROM users WHERE age < 0");
+        assert_eq!(iter.columns().len(), 2);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.columns()[1].name(), "age");
+        drop(iter);
+
+        let mut iter = conn.query_iter("SELECT * FROM no_such_table");
+        assert!(iter.columns().is_empty());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn columns() {
+        let conn = prepare();
+        conn.execute(
+            "CREATE TEMPORARY TABLE items (id INTEGER NOT NULL, price DECIMAL(10, 2), data BLOB)",
+        )
+        .unwrap();
+        conn.execute("INSERT INTO items VALUES (1, 9.99, NULL)")
+            .unwrap();
+
+        let rows = conn
+            .rows("SELECT id, price AS cost, data, id + 1 AS next FROM items")
+            .unwrap();
+        let columns = rows[0].columns();
+        assert_eq!(columns.len(), 4);
+        assert_eq!(columns[0].name(), "id");
+        assert_eq!(columns[0].decl_type(), Some("INT"));
+        assert_eq!(columns[0].nullable(), Some(false));
+        assert_eq!(columns[0].table(), Some("items"));
+        assert_eq!(columns[1].name(), "cost");
+        assert_eq!(columns[1].origin_name(), Some("price"));
+        assert_eq!(columns[1].decl_type(), Some("DECIMAL"));
+        assert_eq!(columns[1].nullable(), Some(true));
+        assert_eq!(columns[1].precision(), Some(10));
+        assert_eq!(columns[1].scale(), Some(2));
+        assert_eq!(columns[2].decl_type(), Some("BLOB"));
+        assert_eq!(columns[3].name(), "next");
+        assert_eq!(columns[3].decl_type(), Some("BIGINT"));
+        assert_eq!(columns[3].table(), None);
+    }
+
+    #[test]
+    fn duplicate_columns() {
+        let conn = prepare();
+        conn.execute(
+            "CREATE TEMPORARY TABLE pets (id INTEGER, owner TEXT); INSERT INTO pets VALUES (7, 'Alice');",
+        )
+        .unwrap();
+        conn.execute("CREATE TEMPORARY TABLE owners (id INTEGER, name TEXT); INSERT INTO owners VALUES (1, 'Alice');")
+            .unwrap();
+
+        let rows = conn
+            .rows("SELECT pets.id, owners.id FROM pets JOIN owners ON pets.owner = owners.name")
+            .unwrap();
+        let row = &rows[0];
+        assert_eq!(row.column_count(), 2);
+        assert_eq!(row.column_names(), ["id", "id"]);
+        assert_eq!(row.get("id"), Some("7"));
+        assert_eq!(row.get(1), Some("1"));
+        assert_eq!(row.get_all("id"), [Some("7"), Some("1")]);
+        assert_eq!(row.column_indices("id"), [0, 1]);
+        assert_eq!(row.get("pets.id"), Some("7"));
+        assert_eq!(row.get_into::<_, i32>("owners.id"), Ok(1));
+        assert_eq!(row.get("users.id"), None);
+        assert_eq!(row.get_all("owners.id"), [Some("1")]);
+        assert_eq!(row.column_indices("pets.id"), [0]);
+        assert_eq!(row.get_all("name"), Vec::<Option<&str>>::new());
+        assert_eq!(row.iter().collect::<Vec<_>>(), ["7", "1"]);
+    }
+
+    #[test]
+    fn result_sets() {
+        let conn = prepare();
+        let sets = conn
+            .result_sets(
+                r#"INSERT INTO users VALUES ('Dave', 7);
+                   UPDATE users SET age = age + 1 WHERE age < 50;
+                   SELECT name FROM users WHERE age = 8;
+                   SELECT name, age FROM users WHERE age < 0;"#,
+            )
+            .unwrap();
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets[0].rows_affected(), 1);
+        assert!(sets[0].columns().is_empty());
+        assert_eq!(sets[1].rows_affected(), 2);
+        assert_eq!(sets[2].rows()[0].get("name"), Some("Dave"));
+        assert_eq!(sets[3].columns().len(), 2);
+        assert_eq!(sets[3].rows(), []);
+
+        let age = 43;
+        let sets = conn
+            .result_sets(query!("SELECT name FROM users WHERE age = {age}"))
+            .unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].rows()[0].get("name"), Some("Alice"));
+
+        let mut sets = conn.result_sets_iter("SELECT 1; SELECT * FROM no_such_table; SELECT 3;");
+        assert_eq!(sets.next().unwrap().unwrap().rows()[0].get(0), Some("1"));
+        assert_eq!(
+            conn.rows("SELECT 1"),
+            err!("connection is busy with an unfinished result")
+        );
+        assert!(sets.next().unwrap().is_err());
+        assert!(sets.next().is_none());
+        assert!(conn.rows("SELECT 1").is_ok());
+    }
+
+    #[test]
+    fn query_one() {
+        let conn = prepare();
+        let row = conn
+            .query_one("SELECT name, age FROM users WHERE name = 'Bob'")
+            .unwrap();
+        assert_eq!(row.get_into::<_, i32>("age"), Ok(69));
+        assert_eq!(
+            conn.query_one("SELECT name FROM users WHERE age < 0"),
+            Err(Error::NoRows)
+        );
+        assert_eq!(
+            conn.query_one("SELECT name FROM users"),
+            Err(Error::TooManyRows)
+        );
+        assert!(conn.query_one("SELECT * FROM no_such_table").is_err());
+
+        let age = 42;
+        let row = conn
+            .query_opt(query!("SELECT name FROM users WHERE age = {age}"))
+            .unwrap();
+        assert_eq!(row.unwrap().get("name"), Some("Alice"));
+        assert_eq!(
+            conn.query_opt("SELECT name FROM users WHERE age < 0"),
+            Ok(None)
+        );
+        assert_eq!(
+            conn.query_opt("SELECT name FROM users"),
+            Err(Error::TooManyRows)
+        );
+        assert!(conn.query_opt("SELECT * FROM no_such_table").is_err());
+    }
+
+    #[test]
+    fn iterate_rows() {
+        let conn = prepare();
+        let mut names = Vec::new();
+        conn.iterate_rows("SELECT name, age FROM users ORDER BY age", |row| {
+            assert_eq!(row.column_count(), 2);
+            names.push(row.get("name").unwrap().to_string());
+            names.len() < 2
+        })
+        .unwrap();
+        assert_eq!(names, ["Alice", "Carol"]);
+
+        let mut calls = 0;
+        conn.iterate("SELECT name FROM users", |_| {
+            calls += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(conn.rows("SELECT name FROM users").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn execute_with_result() {
+        let conn = prepare();
+        conn.execute(
+            "CREATE TEMPORARY TABLE items (id INTEGER AUTO_INCREMENT PRIMARY KEY, name TEXT)",
+        )
+        .unwrap();
+        let result = conn
+            .execute_with_result("INSERT INTO items (name) VALUES ('a'), ('b')")
+            .unwrap();
+        assert_eq!(result.rows_affected(), 2);
+        assert_eq!(result.last_insert_id(), Some(1));
+
+        let age = 45;
+        let result = conn
+            .execute_with_result(query!("UPDATE users SET age = age + 1 WHERE age > {age}"))
+            .unwrap();
+        assert_eq!(result.rows_affected(), 2);
+        assert_eq!(result.last_insert_id(), None);
+
+        let result = conn
+            .execute_with_result("DELETE FROM users WHERE age < 0")
+            .unwrap();
+        assert_eq!(result, ExecResult::default());
+        assert!(conn
+            .execute_with_result("INSERT INTO no_such_table VALUES (1)")
+            .is_err());
+    }
+
+    #[test]
+    fn transaction() {
+        let conn = prepare();
+        let count = |conn: &concatsql::Connection| {
+            conn.query_scalar::<i64>("SELECT count(*) FROM users")
+                .unwrap()
+        };
+
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO users VALUES ('Dave', 7)").unwrap();
+        assert!(conn.transaction().is_err());
+        tx.commit().unwrap();
+        assert_eq!(count(&conn), 4);
+
+        {
+            let tx = conn.transaction().unwrap();
+            tx.execute("DELETE FROM users").unwrap();
+            assert_eq!(count(&tx), 0);
+        }
+        assert_eq!(count(&conn), 4);
+
+        let insert = |conn: &concatsql::Connection| -> Result<(), Error> {
+            let tx = conn.transaction()?;
+            tx.execute("INSERT INTO users VALUES ('Eve', 8)")?;
+            tx.execute("INSERT INTO no_such_table VALUES (1)")?;
+            tx.commit()
+        };
+        assert!(insert(&conn).is_err());
+        assert_eq!(count(&conn), 4);
+
+        let mut tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO users VALUES ('Eve', 8)").unwrap();
+        {
+            let mut sp = tx.savepoint().unwrap();
+            sp.execute("INSERT INTO users VALUES ('Frank', 9)").unwrap();
+            let sp2 = sp.savepoint().unwrap();
+            sp2.execute("DELETE FROM users").unwrap();
+            sp2.rollback().unwrap();
+            sp.commit().unwrap();
+        }
+        {
+            let sp = tx.savepoint().unwrap();
+            sp.execute("DELETE FROM users").unwrap();
+        }
+        tx.commit().unwrap();
+        assert_eq!(count(&conn), 6);
+
+        let tx = conn
+            .build_transaction()
+            .isolation_level(IsolationLevel::Serializable)
+            .read_only(true)
+            .start()
+            .unwrap();
+        assert!(tx.execute("DELETE FROM users").is_err());
+        drop(tx);
+        conn.execute("DELETE FROM users WHERE age < 10").unwrap();
+        assert_eq!(count(&conn), 3);
+    }
+
+    #[test]
+    fn statement_cache() {
+        let conn = prepare();
+        for age in 0..3 {
+            let name = format!("User{}", age);
+            conn.execute(query!("INSERT INTO users VALUES ({name}, {age})"))
+                .unwrap();
+        }
+        let age = 10;
+        let sql = query!("SELECT * FROM users WHERE age < {age}");
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+
+        // The iterator holds the connection, so the cached statement is free again once it is done.
+        let mut rows = conn.query_iter(&sql);
+        assert!(rows.next().unwrap().is_ok());
+        assert!(conn.rows(&sql).is_err());
+        assert_eq!(rows.count(), 2);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+
+        conn.execute("ALTER TABLE users ADD COLUMN note TEXT")
+            .unwrap();
+        assert_eq!(conn.rows(&sql).unwrap()[0].column_count(), 3);
+        assert_eq!(
+            conn.query_iter(&sql)
+                .next()
+                .unwrap()
+                .unwrap()
+                .column_count(),
+            3
+        );
+
+        conn.set_statement_cache_capacity(0);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        conn.set_statement_cache_capacity(1);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        assert_eq!(conn.query_scalar::<i64>(query!("SELECT {age}")), Ok(10));
+        conn.clear_statement_cache();
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn prepared_statement() {
+        let conn = prepare();
+        let (name, age) = ("", 0);
+        let insert = conn
+            .prepare(query!("INSERT INTO users VALUES ({name}, {age})"))
+            .unwrap();
+        assert_eq!(insert.param_count(), 2);
+        insert.execute(params!["Dave", 20]).unwrap();
+        assert_eq!(
+            insert
+                .execute_with_result(params!["Eve", 30])
+                .unwrap()
+                .rows_affected(),
+            1
+        );
+        assert!(insert.execute(params!["Frank"]).is_err());
+        assert!(insert.execute(params!["Frank", 1, 2]).is_err());
+
+        let select = conn
+            .prepare("SELECT name FROM users WHERE age < ? ORDER BY age")
+            .unwrap();
+        assert_eq!(select.param_count(), 1);
+        let rows = select.rows(params![35]).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), Some("Dave"));
+        assert_eq!(select.query_iter(params![25]).count(), 1);
+        assert!(select.query_iter(&[]).next().unwrap().is_err());
+
+        // A statement stays prepared whatever the capacity and the queries run in between.
+        conn.set_statement_cache_capacity(0);
+        for age in 0..40 {
+            conn.rows(query!("SELECT {age}")).unwrap();
+        }
+        assert_eq!(select.rows(params![35]).unwrap().len(), 2);
+        insert.execute(params!["Frank", 40]).unwrap();
+
+        assert!(conn.prepare("SELECT * FROM missing").is_err());
+    }
+
+    #[test]
+    fn execute_many() {
+        let conn = prepare();
+        let users = [("Dave".to_string(), 20), ("Eve".to_string(), 30)];
+        let rows = users
+            .iter()
+            .map(|(name, age)| vec![name as &dyn ToValue, age]);
+        assert_eq!(
+            conn.execute_many("INSERT INTO users VALUES (?, ?)", rows, false),
+            Ok(2)
+        );
+
+        let (name, age) = ("", 0);
+        let sql = query!("UPDATE users SET age = {age} WHERE name = {name}");
+        assert_eq!(
+            conn.execute_many(&sql, vec![params![1, "Dave"], params![2, "Nobody"]], false),
+            Ok(1)
+        );
+
+        // In its own transaction, a bad parameter set rolls back the whole batch,
+        // even if errors are ignored.
+        let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+        assert!(conn
+            .execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], true)
+            .is_err());
+        conn.error_level(ErrorLevel::AlwaysOk);
+        assert_eq!(
+            conn.execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], true),
+            Ok(0)
+        );
+        conn.error_level(ErrorLevel::Debug);
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+
+        // Otherwise the sets before the bad one are applied.
+        assert!(conn
+            .execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], false)
+            .is_err());
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(6)
+        );
+        conn.execute("DELETE FROM users WHERE name = 'Frank'")
+            .unwrap();
+
+        // Inside a transaction, the batch is part of it.
+        let tx = conn.transaction().unwrap();
+        assert!(tx
+            .execute_many(&sql, vec![params!["Frank", 40]], true)
+            .is_err());
+        assert_eq!(
+            tx.execute_many(&sql, vec![params!["Frank", 40]], false),
+            Ok(1)
+        );
+        tx.rollback().unwrap();
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+
+        // So is one begun with plain SQL.
+        conn.execute("BEGIN").unwrap();
+        assert_eq!(
+            conn.execute_many(&sql, vec![params!["Frank", 40]], false),
+            Ok(1)
+        );
+        conn.execute("ROLLBACK").unwrap();
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+    }
+
     #[test]
     fn question() {
         let conn = prepare();
@@ -4,7 +4,7 @@
 #[cfg(debug_assertions)]
 mod postgres {
     use concatsql::prelude::*;
-    use concatsql::{Error, ErrorLevel};
+    use concatsql::{Error, ErrorLevel, ExecResult, IpNetwork, IsolationLevel, MacAddr};
 
     macro_rules! err {
         () => {
@@ -399,6 +399,649 @@ mod postgres {
         }
     }
 
+    #[test]
+    fn null() {
+        let conn = prepare();
+        let name: Option<&str> = None;
+        let age: Option<i32> = None;
+        let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+        conn.execute(&sql).unwrap();
+        let sql = query!("INSERT INTO users VALUES ('Dave', ") + age + query!(")");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn
+            .rows("SELECT name, age FROM users WHERE age IS NULL ORDER BY name")
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), Some("Dave"));
+        assert_eq!(rows[0].get("age"), None);
+        assert_eq!(rows[1].get("name"), None);
+        assert_eq!(rows[1].get("age"), None);
+        assert_eq!(rows[1].get_into::<_, Option<i32>>("age"), Ok(None));
+        assert_eq!(
+            rows[1].get_into::<_, i32>("age"),
+            Err(Error::UnexpectedNull("age".to_string()))
+        );
+    }
+
+    #[test]
+    fn interval() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE slas (name TEXT, span INTERVAL)")
+            .unwrap();
+        let window = std::time::Duration::from_secs(90 * 60);
+        let sql = query!("INSERT INTO slas VALUES ('std', {window})");
+        conn.execute(&sql).unwrap();
+        let window = chrono::Duration::hours(-26) + chrono::Duration::microseconds(5);
+        let sql = query!("INSERT INTO slas VALUES ('chrono', {window})");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM slas ORDER BY name").unwrap();
+        assert_eq!(rows[0].get("span"), Some("-25:59:59.999995"));
+        assert_eq!(
+            rows[0].get_into::<_, chrono::Duration>("span"),
+            Ok(chrono::Duration::hours(-26) + chrono::Duration::microseconds(5))
+        );
+        assert!(rows[0].get_into::<_, std::time::Duration>("span").is_err());
+        assert_eq!(rows[1].get("span"), Some("01:30:00.000000"));
+        assert_eq!(
+            rows[1].get_into::<_, std::time::Duration>("span"),
+            Ok(std::time::Duration::from_secs(90 * 60))
+        );
+
+        // The binary (extended) and text (simple) protocols agree on months and years.
+        let sql = "SELECT interval '1 year 1 mon 1 day 01:00:00' AS i";
+        let expected = Ok(chrono::Duration::days(391) + chrono::Duration::hours(1));
+        let rows = conn.rows(sql).unwrap();
+        assert_eq!(rows[0].get_into::<_, chrono::Duration>("i"), expected);
+        let sets = conn.result_sets(sql).unwrap();
+        assert_eq!(
+            sets[0].rows()[0].get_value("i"),
+            Some(&Value::Text("1 year 1 mon 1 day 01:00:00".into()))
+        );
+        assert_eq!(
+            sets[0].rows()[0].get_into::<_, chrono::Duration>("i"),
+            expected
+        );
+    }
+
+    #[test]
+    fn int128() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE ids (id NUMERIC(39, 0))")
+            .unwrap();
+        let min = i128::MIN;
+        let max = u128::MAX;
+        let sql = query!("INSERT INTO ids VALUES ({min}), ({max}), (0)");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT id FROM ids ORDER BY id").unwrap();
+        assert_eq!(rows[0].get_into::<_, i128>("id"), Ok(i128::MIN));
+        assert_eq!(rows[1].get("id"), Some("0"));
+        assert_eq!(rows[2].get_into::<_, u128>("id"), Ok(u128::MAX));
+
+        let sql = query!("SELECT ") + concatsql::AsBlob(-2i128) + query!("::bytea AS id");
+        let mut bytes = vec![0xff; 16];
+        bytes[15] = 0xfe;
+        let rows = conn.rows(&sql).unwrap();
+        assert_eq!(rows[0].get_value("id"), Some(&Value::Bytes(bytes)));
+    }
+
+    #[test]
+    fn network() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE hosts (inet INET, cidr CIDR, mac MACADDR)")
+            .unwrap();
+        let inet: IpNetwork = "192.168.0.10/24".parse().unwrap();
+        let cidr: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let mac: MacAddr = "08:00:2b:01:02:03".parse().unwrap();
+        let sql = query!("INSERT INTO hosts VALUES ({inet}, {cidr}, {mac})");
+        conn.execute(&sql).unwrap();
+        let host: IpNetwork = "::1".parse().unwrap();
+        let sql = query!("INSERT INTO hosts VALUES ({host}, {host}, NULL)");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM hosts ORDER BY cidr").unwrap();
+        assert_eq!(rows[0].get("inet"), Some("192.168.0.10/24"));
+        assert_eq!(rows[0].get("cidr"), Some("10.0.0.0/8"));
+        assert_eq!(rows[0].get("mac"), Some("08:00:2b:01:02:03"));
+        assert_eq!(rows[0].get_into::<_, IpNetwork>("inet"), Ok(inet));
+        assert_eq!(rows[0].get_into::<_, IpNetwork>("cidr"), Ok(cidr));
+        assert_eq!(rows[0].get_into::<_, MacAddr>("mac"), Ok(mac));
+        assert_eq!(rows[1].get("inet"), Some("::1"));
+        assert_eq!(rows[1].get("cidr"), Some("::1/128"));
+        assert_eq!(rows[1].get_into::<_, IpNetwork>("inet"), Ok(host));
+        assert_eq!(rows[1].get_into::<_, IpNetwork>("cidr"), Ok(host));
+
+        let host_bits: IpNetwork = "10.0.0.1/8".parse().unwrap();
+        let sql = query!("INSERT INTO hosts (cidr) VALUES ({host_bits})");
+        assert!(conn.execute(&sql).is_err());
+    }
+
+    #[test]
+    fn get_bytes() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE files (data BYTEA, name TEXT)")
+            .unwrap();
+        let data = b"ABCD".to_vec();
+        let sql = query!("INSERT INTO files VALUES ({data}, 'ABCD')");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM files").unwrap();
+        assert_eq!(rows[0].get_bytes("data"), Some(&b"ABCD"[..]));
+        assert_eq!(rows[0].get_value("data"), Some(&Value::Bytes(data.clone())));
+        assert_eq!(rows[0].get_into::<_, Vec<u8>>("data"), Ok(data));
+        assert_eq!(rows[0].get("data"), Some("41424344"));
+        assert_eq!(rows[0].get_bytes("name"), Some(&b"ABCD"[..]));
+        assert_eq!(rows[0].get_into::<_, Vec<u8>>("name"), Ok(b"ABCD".to_vec()));
+
+        // The simple protocol sends `bytea` as `\x` hex text.
+        let sets = conn.result_sets("SELECT * FROM files").unwrap();
+        let row = &sets[0].rows()[0];
+        assert_eq!(row.get("data"), Some("\\x41424344"));
+        assert_eq!(row.get_bytes("data"), Some(&b"ABCD"[..]));
+        assert_eq!(row.get_into::<_, Vec<u8>>("data"), Ok(b"ABCD".to_vec()));
+        assert_eq!(row.get_into::<_, Vec<u8>>("name"), Ok(b"ABCD".to_vec()));
+    }
+
+    #[test]
+    fn query_iter() {
+        let conn = prepare();
+        let mut iter = conn.query_iter("SELECT name, age FROM users ORDER BY age");
+        let row = iter.next().unwrap().unwrap();
+        assert_eq!(row.get("name"), Some("Alice"));
+        assert_eq!(row.get_into::<_, i64>("age"), Ok(42));
+        // The iterator holds the connection until it is dropped.
+        assert_eq!(
+            conn.rows("SELECT 1"),
+            err!("connection is busy with an unfinished result")
+        );
+        drop(iter);
+
+        // The connection is usable again once the iterator is dropped.
+        conn.execute("INSERT INTO users VALUES ('Dave', 7)")
+            .unwrap();
+        let mut names = conn
+            .query_iter(query!("SELECT name FROM users WHERE age < ") + 50)
+            .map(|row| row.unwrap().get_into::<_, String>(0).unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["Alice", "Dave"]);
+
+        // And as soon as the rows run out.
+        let mut iter = conn.query_iter("SELECT name FROM users");
+        assert_eq!(iter.by_ref().count(), 4);
+        assert!(conn.rows("SELECT 1").is_ok());
+        drop(iter);
+
+        // The columns are known before the first row, and without any.
+        let mut iter = conn.query_iter("SELECT name, age FROM users ORDER BY age");
+        assert_eq!(iter.columns()[0].name(), "name");
+        assert_eq!(iter.next().unwrap().unwrap().get(0), Some("Dave"));
+        drop(iter);
+        let mut iter = conn.query_iter("SELECT name, age FROM users WHERE age < 0");
+        assert_eq!(iter.columns().len(), 2);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.columns()[1].name(), "age");
+        drop(iter);
+
+        let mut iter = conn.query_iter("SELECT * FROM no_such_table");
+        assert!(iter.columns().is_empty());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn columns() {
+        let conn = prepare();
+        conn.execute("CREATE TEMPORARY TABLE items (id INTEGER NOT NULL, price NUMERIC(10, 2))")
+            .unwrap();
+        conn.execute("INSERT INTO items VALUES (1, 9.99)").unwrap();
+
+        let rows = conn
+            .rows("SELECT id, price AS cost, id::bigint + 1 AS next FROM items")
+            .unwrap();
+        let columns = rows[0].columns();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0].name(), "id");
+        assert_eq!(columns[0].decl_type(), Some("int4"));
+        assert_eq!(columns[0].nullable(), None);
+        assert_eq!(columns[0].table(), Some("items"));
+        assert_eq!(columns[0].precision(), None);
+        assert_eq!(columns[1].name(), "cost");
+        assert_eq!(columns[1].origin_name(), Some("price"));
+        assert_eq!(columns[1].decl_type(), Some("numeric"));
+        assert_eq!(columns[1].precision(), Some(10));
+        assert_eq!(columns[1].scale(), Some(2));
+        assert_eq!(columns[2].decl_type(), Some("int8"));
+        assert_eq!(columns[2].table(), None);
+        assert_eq!(rows[0].get("items.price"), Some("9.99"));
+    }
+
+    #[test]
+    fn duplicate_columns() {
+        let conn = prepare();
+        conn.execute(
+            "CREATE TEMPORARY TABLE pets (id INTEGER, owner TEXT); INSERT INTO pets VALUES (7, 'Alice');",
+        )
+        .unwrap();
+        conn.execute("CREATE TEMPORARY TABLE owners (id INTEGER, name TEXT); INSERT INTO owners VALUES (1, 'Alice');")
+            .unwrap();
+
+        let rows = conn
+            .rows("SELECT pets.id, owners.id FROM pets JOIN owners ON pets.owner = owners.name")
+            .unwrap();
+        let row = &rows[0];
+        assert_eq!(row.column_count(), 2);
+        assert_eq!(row.column_names(), ["id", "id"]);
+        assert_eq!(row.get("id"), Some("7"));
+        assert_eq!(row.get(1), Some("1"));
+        assert_eq!(row.get_all("id"), [Some("7"), Some("1")]);
+        assert_eq!(row.column_indices("id"), [0, 1]);
+        assert_eq!(row.get("pets.id"), Some("7"));
+        assert_eq!(row.get_into::<_, i32>("owners.id"), Ok(1));
+        assert_eq!(row.get("users.id"), None);
+        assert_eq!(row.get_all("owners.id"), [Some("1")]);
+        assert_eq!(row.column_indices("pets.id"), [0]);
+        assert_eq!(row.get_all("name"), Vec::<Option<&str>>::new());
+    }
+
+    #[test]
+    fn result_sets() {
+        let conn = prepare();
+        let sets = conn
+            .result_sets(
+                r#"INSERT INTO users VALUES ('Dave', 7);
+                   UPDATE users SET age = age + 1 WHERE age < 50;
+                   SELECT name, age FROM users WHERE age = 8;
+                   SELECT name, age FROM users WHERE age < 0;"#,
+            )
+            .unwrap();
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets[0].rows_affected(), 1);
+        assert!(sets[0].columns().is_empty());
+        assert_eq!(sets[1].rows_affected(), 2);
+        assert_eq!(sets[2].rows()[0].get("name"), Some("Dave"));
+        assert_eq!(
+            sets[2].rows()[0].get_value("age"),
+            Some(&Value::Text("8".into()))
+        );
+        assert_eq!(sets[3].columns().len(), 2);
+        // The simple protocol names the columns but does not describe them.
+        assert_eq!(sets[3].columns()[1].decl_type(), None);
+        assert_eq!(sets[3].rows(), []);
+
+        let age = 43;
+        let sets = conn
+            .result_sets(query!("SELECT name, age FROM users WHERE age = {age}"))
+            .unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].columns()[1].decl_type(), Some("int4"));
+        assert_eq!(sets[0].rows()[0].get_value("age"), Some(&Value::I32(43)));
+
+        // The batch has already run, so the connection is free.
+        let sets = conn.result_sets_iter("SELECT 1; SELECT 2;");
+        assert!(conn.rows("SELECT 1").is_ok());
+        assert_eq!(sets.count(), 2);
+
+        let mut sets = conn.result_sets_iter("SELECT * FROM no_such_table");
+        assert!(sets.next().unwrap().is_err());
+        assert!(sets.next().is_none());
+    }
+
+    #[test]
+    fn query_one() {
+        let conn = prepare();
+        let row = conn
+            .query_one("SELECT name, age FROM users WHERE name = 'Bob'")
+            .unwrap();
+        assert_eq!(row.get_into::<_, i32>("age"), Ok(69));
+        assert_eq!(
+            conn.query_one("SELECT name FROM users WHERE age < 0"),
+            Err(Error::NoRows)
+        );
+        assert_eq!(
+            conn.query_one("SELECT name FROM users"),
+            Err(Error::TooManyRows)
+        );
+        assert!(conn.query_one("SELECT * FROM no_such_table").is_err());
+
+        let age = 42;
+        let row = conn
+            .query_opt(query!("SELECT name FROM users WHERE age = {age}"))
+            .unwrap();
+        assert_eq!(row.unwrap().get("name"), Some("Alice"));
+        assert_eq!(
+            conn.query_opt("SELECT name FROM users WHERE age < 0"),
+            Ok(None)
+        );
+        assert_eq!(
+            conn.query_opt("SELECT name FROM users"),
+            Err(Error::TooManyRows)
+        );
+        assert!(conn.query_opt("SELECT * FROM no_such_table").is_err());
+    }
+
+    #[test]
+    fn export() {
+        use concatsql::export::Export;
+
+        let conn = prepare();
+        let mut out = Vec::new();
+        let count = Export::csv()
+            .mask("age")
+            .write(&conn, "SELECT name, age FROM users ORDER BY age", &mut out)
+            .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(out, b"name,age\r\nAlice,***\r\nCarol,***\r\nBob,***\r\n");
+
+        let mut out = Vec::new();
+        Export::json_lines()
+            .write(
+                &conn,
+                "SELECT name, age, NULL AS note FROM users WHERE age = 42",
+                &mut out,
+            )
+            .unwrap();
+        assert_eq!(out, b"{\"name\":\"Alice\",\"age\":42,\"note\":null}\n");
+    }
+
+    #[test]
+    fn iterate_rows() {
+        let conn = prepare();
+        let mut names = Vec::new();
+        conn.iterate_rows("SELECT name, age FROM users ORDER BY age", |row| {
+            assert_eq!(row.column_count(), 2);
+            names.push(row.get("name").unwrap().to_string());
+            names.len() < 2
+        })
+        .unwrap();
+        assert_eq!(names, ["Alice", "Carol"]);
+
+        let mut calls = 0;
+        conn.iterate("SELECT name FROM users", |_| {
+            calls += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(conn.rows("SELECT name FROM users").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn execute_with_result() {
+        let conn = prepare();
+        let result = conn
+            .execute_with_result("INSERT INTO users VALUES ('Dave', 7), ('Eve', 8)")
+            .unwrap();
+        assert_eq!(result.rows_affected(), 2);
+        assert_eq!(result.last_insert_id(), None);
+
+        let age = 45;
+        let result = conn
+            .execute_with_result(query!("UPDATE users SET age = age + 1 WHERE age > {age}"))
+            .unwrap();
+        assert_eq!(result.rows_affected(), 2);
+
+        let result = conn
+            .execute_with_result("DELETE FROM users WHERE age < 8; DELETE FROM users WHERE age < 0")
+            .unwrap();
+        assert_eq!(result, ExecResult::default());
+        assert!(conn
+            .execute_with_result("INSERT INTO no_such_table VALUES (1)")
+            .is_err());
+    }
+
+    #[test]
+    fn transaction() {
+        let conn = prepare();
+        let count = |conn: &concatsql::Connection| {
+            conn.query_scalar::<i64>("SELECT count(*) FROM users")
+                .unwrap()
+        };
+
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO users VALUES ('Dave', 7)").unwrap();
+        assert!(conn.transaction().is_err());
+        tx.commit().unwrap();
+        assert_eq!(count(&conn), 4);
+
+        {
+            let tx = conn.transaction().unwrap();
+            tx.execute("DELETE FROM users").unwrap();
+            assert_eq!(count(&tx), 0);
+        }
+        assert_eq!(count(&conn), 4);
+
+        let insert = |conn: &concatsql::Connection| -> Result<(), Error> {
+            let tx = conn.transaction()?;
+            tx.execute("INSERT INTO users VALUES ('Eve', 8)")?;
+            tx.execute("INSERT INTO no_such_table VALUES (1)")?;
+            tx.commit()
+        };
+        assert!(insert(&conn).is_err());
+        assert_eq!(count(&conn), 4);
+
+        let mut tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO users VALUES ('Eve', 8)").unwrap();
+        {
+            let mut sp = tx.savepoint().unwrap();
+            sp.execute("INSERT INTO users VALUES ('Frank', 9)").unwrap();
+            let sp2 = sp.savepoint().unwrap();
+            sp2.execute("DELETE FROM users").unwrap();
+            sp2.rollback().unwrap();
+            sp.commit().unwrap();
+        }
+        {
+            let sp = tx.savepoint().unwrap();
+            sp.execute("DELETE FROM users").unwrap();
+        }
+        tx.commit().unwrap();
+        assert_eq!(count(&conn), 6);
+
+        let tx = conn
+            .build_transaction()
+            .isolation_level(IsolationLevel::Serializable)
+            .read_only(true)
+            .start()
+            .unwrap();
+        // Temporary tables stay writable in a read-only transaction, so create a table instead.
+        assert!(tx
+            .execute("CREATE TABLE concatsql_read_only (id INTEGER)")
+            .is_err());
+        drop(tx);
+        conn.execute("DELETE FROM users WHERE age < 10").unwrap();
+        assert_eq!(count(&conn), 3);
+
+        // A failed COMMIT is reported whatever the error level, and rolls back.
+        conn.execute(
+            "CREATE TEMPORARY TABLE owners (name TEXT PRIMARY KEY);
+             CREATE TEMPORARY TABLE pets (owner TEXT REFERENCES owners DEFERRABLE INITIALLY DEFERRED);",
+        )
+        .unwrap();
+        conn.error_level(ErrorLevel::AlwaysOk);
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO pets VALUES ('Nobody')").unwrap();
+        assert!(tx.commit().is_err());
+        conn.error_level(ErrorLevel::Debug);
+        assert_eq!(conn.query_scalar::<i64>("SELECT count(*) FROM pets"), Ok(0));
+        assert!(conn.transaction().is_ok());
+    }
+
+    #[test]
+    fn statement_cache() {
+        let conn = prepare();
+        for age in 0..3 {
+            let name = format!("User{}", age);
+            conn.execute(query!("INSERT INTO users VALUES ({name}, {age})"))
+                .unwrap();
+        }
+        let age = 10;
+        let sql = query!("SELECT * FROM users WHERE age < {age}");
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+
+        // The iterator holds the connection, so the cached statement is free again once it is done.
+        let mut rows = conn.query_iter(&sql);
+        assert!(rows.next().unwrap().is_ok());
+        assert!(conn.rows(&sql).is_err());
+        assert_eq!(rows.count(), 2);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+
+        conn.execute("ALTER TABLE users ADD COLUMN note TEXT")
+            .unwrap();
+        // PostgreSQL rejects the cached statement now that its result columns changed.
+        assert!(conn.rows(&sql).is_err());
+        conn.clear_statement_cache();
+        assert_eq!(conn.rows(&sql).unwrap()[0].column_count(), 3);
+        assert_eq!(
+            conn.query_iter(&sql)
+                .next()
+                .unwrap()
+                .unwrap()
+                .column_count(),
+            3
+        );
+
+        conn.set_statement_cache_capacity(0);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        conn.set_statement_cache_capacity(1);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        assert_eq!(
+            conn.query_scalar::<i64>(query!("SELECT {age}::INTEGER")),
+            Ok(10)
+        );
+        conn.clear_statement_cache();
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn prepared_statement() {
+        let conn = prepare();
+        let (name, age) = ("", 0);
+        let insert = conn
+            .prepare(query!("INSERT INTO users VALUES ({name}, {age})"))
+            .unwrap();
+        assert_eq!(insert.param_count(), 2);
+        insert.execute(params!["Dave", 20]).unwrap();
+        assert_eq!(
+            insert
+                .execute_with_result(params!["Eve", 30])
+                .unwrap()
+                .rows_affected(),
+            1
+        );
+        assert!(insert.execute(params!["Frank"]).is_err());
+        assert!(insert.execute(params!["Frank", 1, 2]).is_err());
+
+        let select = conn
+            .prepare("SELECT name FROM users WHERE age < $1 ORDER BY age")
+            .unwrap();
+        assert_eq!(select.param_count(), 1);
+        let rows = select.rows(params![35]).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), Some("Dave"));
+        assert_eq!(select.query_iter(params![25]).count(), 1);
+        assert!(select.query_iter(&[]).next().unwrap().is_err());
+
+        // A statement stays prepared whatever the capacity and the queries run in between.
+        conn.set_statement_cache_capacity(0);
+        for age in 0..40 {
+            conn.rows(query!("SELECT {age}::INTEGER")).unwrap();
+        }
+        assert_eq!(select.rows(params![35]).unwrap().len(), 2);
+        insert.execute(params!["Frank", 40]).unwrap();
+
+        // Without placeholders, it is still run as a prepared statement, kept on the server
+        // until it is dropped.
+        let prepared = "SELECT count(*) FROM pg_prepared_statements \
+                        WHERE statement = 'DELETE FROM users WHERE name = ''Frank'''";
+        let delete = conn
+            .prepare("DELETE FROM users WHERE name = 'Frank'")
+            .unwrap();
+        assert_eq!(delete.param_count(), 0);
+        assert_eq!(delete.execute_with_result(&[]).unwrap().rows_affected(), 1);
+        assert_eq!(conn.rows(prepared).unwrap()[0].get_into::<_, i64>(0), Ok(1));
+        drop(delete);
+        assert_eq!(conn.rows(prepared).unwrap()[0].get_into::<_, i64>(0), Ok(0));
+
+        assert!(conn.prepare("SELECT * FROM missing").is_err());
+    }
+
+    #[test]
+    fn execute_many() {
+        let conn = prepare();
+        let users = [("Dave".to_string(), 20), ("Eve".to_string(), 30)];
+        let rows = users
+            .iter()
+            .map(|(name, age)| vec![name as &dyn ToValue, age]);
+        assert_eq!(
+            conn.execute_many("INSERT INTO users VALUES ($1, $2)", rows, false),
+            Ok(2)
+        );
+
+        let (name, age) = ("", 0);
+        let sql = query!("UPDATE users SET age = {age} WHERE name = {name}");
+        assert_eq!(
+            conn.execute_many(&sql, vec![params![1, "Dave"], params![2, "Nobody"]], false),
+            Ok(1)
+        );
+
+        // In its own transaction, a bad parameter set rolls back the whole batch,
+        // even if errors are ignored.
+        let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+        assert!(conn
+            .execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], true)
+            .is_err());
+        conn.error_level(ErrorLevel::AlwaysOk);
+        assert_eq!(
+            conn.execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], true),
+            Ok(0)
+        );
+        conn.error_level(ErrorLevel::Debug);
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+
+        // Otherwise the sets before the bad one are applied.
+        assert!(conn
+            .execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], false)
+            .is_err());
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(6)
+        );
+        conn.execute("DELETE FROM users WHERE name = 'Frank'")
+            .unwrap();
+
+        // Inside a transaction, the batch is part of it.
+        let tx = conn.transaction().unwrap();
+        assert!(tx
+            .execute_many(&sql, vec![params!["Frank", 40]], true)
+            .is_err());
+        assert_eq!(
+            tx.execute_many(&sql, vec![params!["Frank", 40]], false),
+            Ok(1)
+        );
+        tx.rollback().unwrap();
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+
+        // So is one begun with plain SQL.
+        conn.execute("BEGIN").unwrap();
+        assert_eq!(
+            conn.execute_many(&sql, vec![params!["Frank", 40]], false),
+            Ok(1)
+        );
+        conn.execute("ROLLBACK").unwrap();
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+    }
+
     #[test]
     fn question() {
         let conn = prepare();
@@ -4,7 +4,7 @@
 #[cfg(debug_assertions)]
 mod sqlite {
     use concatsql::prelude::*;
-    use concatsql::{Error, ErrorLevel};
+    use concatsql::{Error, ErrorLevel, ExecResult, IpNetwork, IsolationLevel, MacAddr};
 
     macro_rules! err {
         () => {
@@ -244,6 +244,27 @@ mod sqlite {
         assert_eq!(conn.rows(invalid_sql), Ok(Vec::new()));
     }
 
+    #[test]
+    fn bind_error() {
+        let conn = concatsql::sqlite::open(":memory:").unwrap();
+        // The placeholder is commented out, so binding the value fails.
+        let sql = query!("SELECT 1 -- ") + 5;
+
+        // A statement that failed to bind is not stepped, at any error level;
+        // it is reset and returned to the cache.
+        conn.error_level(ErrorLevel::AlwaysOk);
+        for _ in 0..2 {
+            assert_eq!(conn.execute(&sql), Ok(()));
+            assert_eq!(conn.iterate(&sql, |_| unreachable!()), Ok(()));
+            assert_eq!(conn.rows(&sql), Ok(Vec::new()));
+            assert!(conn.query_iter(&sql).next().is_none());
+        }
+        conn.error_level(ErrorLevel::Develop);
+        assert_eq!(conn.execute(&sql), err!("bind error"));
+        assert_eq!(conn.rows(&sql), err!("bind error"));
+        assert_eq!(conn.rows("SELECT 1").unwrap().len(), 1);
+    }
+
     #[test]
     fn error_level_release() {
         let conn = concatsql::sqlite::open(":memory:").unwrap();
@@ -464,6 +485,804 @@ mod sqlite {
         }
     }
 
+    #[test]
+    fn null() {
+        let conn = prepare();
+        let name: Option<&str> = None;
+        let age: Option<i32> = None;
+        let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+        conn.execute(&sql).unwrap();
+        let sql = query!("INSERT INTO users VALUES ('Dave', ") + age + query!(")");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn
+            .rows("SELECT name, age FROM users WHERE age IS NULL ORDER BY name")
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), None);
+        assert_eq!(rows[0].get("age"), None);
+        assert_eq!(rows[1].get("name"), Some("Dave"));
+        assert_eq!(rows[1].get("age"), None);
+        assert_eq!(rows[1].get_into::<_, Option<i32>>("age"), Ok(None));
+        assert_eq!(
+            rows[1].get_into::<_, i32>("age"),
+            Err(Error::UnexpectedNull("age".to_string()))
+        );
+    }
+
+    #[test]
+    fn interval() {
+        let conn = sqlite::open(":memory:").unwrap();
+        conn.execute("CREATE TEMPORARY TABLE slas (name TEXT, window TEXT)")
+            .unwrap();
+        let window = std::time::Duration::from_secs(90 * 60);
+        let sql = query!("INSERT INTO slas VALUES ('std', {window})");
+        conn.execute(&sql).unwrap();
+        let window = chrono::Duration::hours(-26) + chrono::Duration::microseconds(5);
+        let sql = query!("INSERT INTO slas VALUES ('chrono', {window})");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM slas ORDER BY name").unwrap();
+        assert_eq!(rows[0].get("window"), Some("-25:59:59.999995"));
+        assert_eq!(
+            rows[0].get_into::<_, chrono::Duration>("window"),
+            Ok(chrono::Duration::hours(-26) + chrono::Duration::microseconds(5))
+        );
+        assert!(rows[0]
+            .get_into::<_, std::time::Duration>("window")
+            .is_err());
+        assert_eq!(rows[1].get("window"), Some("01:30:00.000000"));
+        assert_eq!(
+            rows[1].get_into::<_, std::time::Duration>("window"),
+            Ok(std::time::Duration::from_secs(90 * 60))
+        );
+    }
+
+    #[test]
+    fn int128() {
+        let conn = sqlite::open(":memory:").unwrap();
+        let min = i128::MIN;
+        let max = u128::MAX;
+        let sql = query!("SELECT {min} AS min, {max} AS max, typeof({min}) AS type");
+        let row = &conn.rows(&sql).unwrap()[0];
+        assert_eq!(row.get_into::<_, i128>("min"), Ok(i128::MIN));
+        assert_eq!(row.get_into::<_, u128>("max"), Ok(u128::MAX));
+        assert_eq!(row.get("type"), Some("text"));
+
+        let sql = query!("SELECT ")
+            + concatsql::AsBlob(-2i128)
+            + query!(" AS min, ")
+            + concatsql::AsBlob(1u128)
+            + query!(" AS max, typeof(")
+            + concatsql::AsBlob(1u128)
+            + query!(") AS type");
+        assert_eq!(
+            sql.simulate(),
+            "SELECT X'FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFE' AS min, \
+             X'00000000000000000000000000000001' AS max, \
+             typeof(X'00000000000000000000000000000001') AS type"
+        );
+        let row = &conn.rows(&sql).unwrap()[0];
+        assert_eq!(row.get("min"), Some("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFE"));
+        assert_eq!(row.get("max"), Some("00000000000000000000000000000001"));
+        assert_eq!(row.get("type"), Some("blob"));
+        assert_eq!(row.get_into::<_, i128>("min"), Ok(-2));
+        assert_eq!(row.get_into::<_, u128>("max"), Ok(1));
+
+        let value = concatsql::AsBlob(42i128);
+        let sql = query!("SELECT {value} AS id, X'2A' AS short");
+        let row = &conn.rows(&sql).unwrap()[0];
+        assert_eq!(row.get_into::<_, i128>("id"), Ok(42));
+        assert_eq!(row.get_into::<_, u128>("id"), Ok(42));
+        assert_eq!(row.get_into::<_, i64>("id"), Err(Error::ParseError));
+        assert_eq!(row.get_into::<_, i128>("short"), Err(Error::ParseError));
+
+        // Unsigned integers past the signed range of their width keep their value.
+        let (a, b, c) = (u32::MAX, i64::MAX as u64 + 1, u64::MAX);
+        let sql = query!("SELECT {a} AS a, {b} AS b, {c} AS c");
+        let row = &conn.rows(&sql).unwrap()[0];
+        assert_eq!(row.get_value("a"), Some(&Value::I64(4294967295)));
+        assert_eq!(row.get_into::<_, u64>("b"), Ok(i64::MAX as u64 + 1));
+        assert_eq!(row.get_into::<_, u64>("c"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn network() {
+        let conn = sqlite::open(":memory:").unwrap();
+        let net: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let mac = MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]);
+        let sql = query!("SELECT {net} AS net, {mac} AS mac");
+        let row = &conn.rows(&sql).unwrap()[0];
+        assert_eq!(row.get("net"), Some("2001:db8::/32"));
+        assert_eq!(row.get("mac"), Some("08:00:2b:01:02:03"));
+        assert_eq!(row.get_into::<_, IpNetwork>("net"), Ok(net));
+        assert_eq!(row.get_into::<_, MacAddr>("mac"), Ok(mac));
+    }
+
+    #[test]
+    fn get_value() {
+        let conn = sqlite::open(":memory:").unwrap();
+        let rows = conn
+            .rows("SELECT 42 AS i, '42' AS t, 1.5 AS f, X'00FF' AS b, NULL AS n")
+            .unwrap();
+        let row = &rows[0];
+        assert_eq!(row.get_value("i"), Some(&Value::I64(42)));
+        assert_eq!(row.get_value("t"), Some(&Value::Text("42".into())));
+        assert_eq!(row.get_value("f"), Some(&Value::F64(1.5)));
+        assert_eq!(row.get_value("b"), Some(&Value::Bytes(vec![0x00, 0xff])));
+        assert_eq!(row.get_value("n"), Some(&Value::Null));
+        assert_eq!(row.get_value("x"), None);
+
+        assert_eq!(row.get("i"), Some("42"));
+        assert_eq!(row.get("f"), Some("1.5"));
+        assert_eq!(row.get("b"), Some("00FF"));
+        assert_eq!(row.get_into::<_, i64>("i"), Ok(42));
+        assert_eq!(row.get_into::<_, u8>("i"), Ok(42));
+        assert_eq!(row.get_into::<_, f64>("i"), Ok(42.0));
+        assert_eq!(row.get_into::<_, Vec<u8>>("b"), Ok(vec![0x00, 0xff]));
+        assert_eq!(row.get_into::<_, i8>("f"), Err(Error::ParseError));
+    }
+
+    #[test]
+    fn real_text() {
+        let conn = sqlite::open(":memory:").unwrap();
+        conn.execute("CREATE TABLE t (r REAL)").unwrap();
+        conn.execute("INSERT INTO t VALUES (1), (-1), (0.1), (1e20)")
+            .unwrap();
+
+        // The text form is SQLite's rendering of the REAL, as `sqlite3_column_text` gives it.
+        let rows = conn.rows("SELECT r FROM t").unwrap();
+        let texts = rows.iter().map(|row| row.get("r")).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            [Some("1.0"), Some("-1.0"), Some("0.1"), Some("1.0e+20")]
+        );
+        assert_eq!(rows[0].get_value("r"), Some(&Value::F64(1.0)));
+
+        let mut texts = Vec::new();
+        conn.iterate("SELECT r FROM t", |pairs| {
+            texts.push(pairs[0].1.map(str::to_string));
+            true
+        })
+        .unwrap();
+        assert_eq!(texts[0].as_deref(), Some("1.0"));
+        let row = conn.query_iter("SELECT r FROM t").next().unwrap().unwrap();
+        assert_eq!(row.get("r"), Some("1.0"));
+    }
+
+    #[test]
+    fn text_bytes() {
+        let conn = sqlite::open(":memory:").unwrap();
+        // SQLite stores TEXT as given, so it may hold a NUL or bytes that are not UTF-8.
+        let row = conn
+            .query_one("SELECT 'a' || char(0) || 'b' AS nul, CAST(X'41FF' AS TEXT) AS invalid")
+            .unwrap();
+        assert_eq!(row.get("nul"), Some("a\0b"));
+        assert_eq!(row.get("invalid"), Some("A\u{fffd}"));
+    }
+
+    #[test]
+    fn get_bytes() {
+        let conn = sqlite::open(":memory:").unwrap();
+        conn.execute("CREATE TABLE files (data BLOB, name TEXT)")
+            .unwrap();
+        let data = b"ABCD".to_vec();
+        let sql = query!("INSERT INTO files VALUES ({data}, 'ABCD')");
+        conn.execute(&sql).unwrap();
+
+        let rows = conn.rows("SELECT * FROM files").unwrap();
+        assert_eq!(rows[0].get_bytes("data"), Some(&b"ABCD"[..]));
+        assert_eq!(rows[0].get_value("data"), Some(&Value::Bytes(data.clone())));
+        assert_eq!(rows[0].get_into::<_, Vec<u8>>("data"), Ok(data));
+        assert_eq!(rows[0].get("data"), Some("41424344"));
+        assert_eq!(rows[0].get_bytes("name"), Some(&b"ABCD"[..]));
+        assert_eq!(rows[0].get_into::<_, Vec<u8>>("name"), Ok(b"ABCD".to_vec()));
+
+        // Text in PostgreSQL's `bytea` hex format is still text.
+        let row = conn.query_one(r"SELECT '\x41' AS name").unwrap();
+        assert_eq!(row.get_into::<_, Vec<u8>>("name"), Ok(br"\x41".to_vec()));
+
+        // A blob is not read as a number through its hex text.
+        let row = conn.query_one("SELECT X'12' AS data").unwrap();
+        assert_eq!(row.get("data"), Some("12"));
+        assert_eq!(row.get_into::<_, i64>("data"), Err(Error::ParseError));
+        assert_eq!(row.get_into::<_, f64>("data"), Err(Error::ParseError));
+        assert_eq!(
+            row.get_into::<_, std::time::Duration>("data"),
+            Err(Error::ParseError)
+        );
+
+        let row = conn.query_one("SELECT X'' AS data").unwrap();
+        assert_eq!(row.get_bytes("data"), Some(&b""[..]));
+        assert_eq!(row.get_value("data"), Some(&Value::Bytes(Vec::new())));
+    }
+
+    #[test]
+    fn rows_as() {
+        #[derive(Debug, PartialEq, FromRow)]
+        struct Audit {
+            created_by: String,
+        }
+        #[derive(Debug, PartialEq, FromRow)]
+        struct User {
+            #[concatsql(rename = "name")]
+            user_name: String,
+            #[concatsql(default)]
+            age: i32,
+            #[concatsql(default)]
+            score: f64,
+            #[concatsql(flatten)]
+            audit: Audit,
+        }
+
+        let conn = prepare();
+        let users = conn
+            .rows_as::<User>("SELECT name, age, 'admin' AS created_by FROM users ORDER BY age")
+            .unwrap();
+        assert_eq!(users.len(), 3);
+        assert_eq!(
+            users[0],
+            User {
+                user_name: "Alice".to_string(),
+                age: 42,
+                score: 0.0,
+                audit: Audit {
+                    created_by: "admin".to_string(),
+                },
+            }
+        );
+        assert_eq!(users[1].user_name, "Carol");
+        assert_eq!(users[2].age, 69);
+
+        assert_eq!(
+            conn.rows_as::<User>("SELECT name, age FROM users"),
+            Err(Error::ColumnNotFound)
+        );
+    }
+
+    #[test]
+    fn tuples() {
+        let conn = prepare();
+        let users = conn
+            .rows_as::<(String, i64, Option<f64>)>("SELECT name, age, NULL FROM users ORDER BY age")
+            .unwrap();
+        assert_eq!(users[0], ("Alice".to_string(), 42, None));
+        assert_eq!(users[2], ("Bob".to_string(), 69, None));
+
+        let rows = conn
+            .rows("SELECT 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12")
+            .unwrap();
+        type Twelve = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+        assert_eq!(
+            rows[0].get_tuple::<Twelve>(),
+            Ok((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12))
+        );
+        assert_eq!(rows[0].get_tuple::<(u8,)>(), Ok((1,)));
+        assert_eq!(
+            conn.rows_as::<(String, i64)>("SELECT name FROM users"),
+            Err(Error::ColumnNotFound)
+        );
+
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT COUNT(*) FROM users"),
+            Ok(3)
+        );
+        assert_eq!(
+            conn.query_scalar::<String>(query!("SELECT name FROM users WHERE age = ") + 50),
+            Ok("Carol".to_string())
+        );
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT age FROM users WHERE age < 0"),
+            Err(Error::NoRows)
+        );
+    }
+
+    #[test]
+    fn query_iter() {
+        let conn = prepare();
+        let mut iter = conn.query_iter("SELECT name, age FROM users ORDER BY age");
+        let row = iter.next().unwrap().unwrap();
+        assert_eq!(row.get("name"), Some("Alice"));
+        assert_eq!(row.get_into::<_, i64>("age"), Ok(42));
+        drop(iter);
+
+        // The connection is usable again once the iterator is dropped.
+        conn.execute("INSERT INTO users VALUES ('Dave', 7)")
+            .unwrap();
+        let mut names = conn
+            .query_iter(query!("SELECT name FROM users WHERE age < ") + 50)
+            .map(|row| row.unwrap().get_into::<_, String>(0).unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["Alice", "Dave"]);
+
+        // The columns are known before the first row, and without any.
+        let mut iter = conn.query_iter("SELECT name, age FROM users ORDER BY age");
+        assert_eq!(iter.columns()[0].name(), "name");
+        assert_eq!(iter.next().unwrap().unwrap().get(0), Some("Dave"));
+        drop(iter);
+        let mut iter = conn.query_iter("SELECT name, age FROM users WHERE age < 0");
+        assert_eq!(iter.columns().len(), 2);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.columns()[1].name(), "age");
+        drop(iter);
+
+        let mut iter = conn.query_iter("SELECT * FROM no_such_table");
+        assert!(iter.columns().is_empty());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn columns() {
+        let conn = prepare();
+        conn.execute("CREATE TABLE items (id INTEGER NOT NULL, price NUMERIC(10, 2))")
+            .unwrap();
+        conn.execute("INSERT INTO items VALUES (1, 9.99)").unwrap();
+
+        let rows = conn
+            .rows("SELECT id, price AS cost, id + 1 AS next FROM items")
+            .unwrap();
+        let columns = rows[0].columns();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0].name(), "id");
+        assert_eq!(columns[0].decl_type(), Some("INTEGER"));
+        assert_eq!(columns[0].nullable(), Some(false));
+        assert_eq!(columns[0].table(), Some("items"));
+        assert_eq!(columns[0].precision(), None);
+        assert_eq!(columns[1].name(), "cost");
+        assert_eq!(columns[1].origin_name(), Some("price"));
+        assert_eq!(columns[1].nullable(), Some(true));
+        assert_eq!(columns[1].precision(), Some(10));
+        assert_eq!(columns[1].scale(), Some(2));
+        assert_eq!(columns[2].name(), "next");
+        assert_eq!(columns[2].decl_type(), None);
+        assert_eq!(columns[2].table(), None);
+        assert_eq!(columns[2].nullable(), None);
+
+        let row = conn
+            .query_iter("SELECT name FROM users")
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.columns()[0].decl_type(), Some("TEXT"));
+    }
+
+    #[test]
+    fn duplicate_columns() {
+        let conn = prepare();
+        conn.execute(
+            "CREATE TABLE pets (id INTEGER, owner TEXT); INSERT INTO pets VALUES (7, 'Alice');",
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE owners (id INTEGER, name TEXT); INSERT INTO owners VALUES (1, 'Alice');",
+        )
+        .unwrap();
+
+        let rows = conn
+            .rows("SELECT pets.id, owners.id FROM pets JOIN owners ON pets.owner = owners.name")
+            .unwrap();
+        let row = &rows[0];
+        assert_eq!(row.column_count(), 2);
+        assert_eq!(row.column_names(), ["id", "id"]);
+        assert_eq!(row.get("id"), Some("7"));
+        assert_eq!(row.get(1), Some("1"));
+        assert_eq!(row.get_all("id"), [Some("7"), Some("1")]);
+        assert_eq!(row.column_indices("id"), [0, 1]);
+        assert_eq!(row.get("pets.id"), Some("7"));
+        assert_eq!(row.get_into::<_, i32>("owners.id"), Ok(1));
+        assert_eq!(row.get("users.id"), None);
+        assert_eq!(row.get_all("owners.id"), [Some("1")]);
+        assert_eq!(row.column_indices("pets.id"), [0]);
+        assert_eq!(row.get_all("name"), Vec::<Option<&str>>::new());
+        assert_eq!(row.iter().collect::<Vec<_>>(), ["7", "1"]);
+    }
+
+    #[test]
+    fn result_sets() {
+        let conn = prepare();
+        let name = "Dave";
+        let age = 7;
+        let sql = query!(
+            r#"INSERT INTO users VALUES ({name}, {age});
+               UPDATE users SET age = age + 1 WHERE age < 50;
+               -- comment
+               SELECT name FROM users WHERE age = {age} + 1;
+               SELECT name, age FROM users WHERE age < 0;"#
+        );
+        let sets = conn.result_sets(&sql).unwrap();
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets[0].rows_affected(), 1);
+        assert!(sets[0].columns().is_empty());
+        assert_eq!(sets[1].rows_affected(), 2);
+        assert_eq!(sets[2].rows_affected(), 0);
+        assert_eq!(sets[2].rows().len(), 1);
+        assert_eq!(sets[2].rows()[0].get("name"), Some("Dave"));
+        assert_eq!(sets[3].columns().len(), 2);
+        assert_eq!(sets[3].rows(), []);
+
+        let mut sets = conn.result_sets_iter("SELECT 1; SELECT * FROM no_such_table; SELECT 3;");
+        assert_eq!(sets.next().unwrap().unwrap().rows()[0].get(0), Some("1"));
+        assert!(sets.next().unwrap().is_err());
+        assert!(sets.next().is_none());
+        drop(sets);
+
+        assert_eq!(conn.result_sets("").unwrap(), []);
+
+        // Placeholders without a value, or values without a placeholder, fail the batch.
+        conn.error_level(ErrorLevel::Develop);
+        let sql = query!("SELECT 1 AS a; SELECT 2 AS b -- ") + 5;
+        assert_eq!(conn.execute(&sql), err!("bind error"));
+        assert_eq!(conn.result_sets(&sql), err!("bind error"));
+        let mut sets = conn.result_sets_iter(&sql);
+        assert!(sets.next().unwrap().is_ok());
+        assert!(sets.next().unwrap().is_ok());
+        assert_eq!(sets.next(), Some(err!("bind error")));
+        assert!(sets.next().is_none());
+        drop(sets);
+        let sql = query!("SELECT 1 AS a; SELECT 2 AS b WHERE 1 = ?");
+        assert_eq!(conn.result_sets(&sql), err!("bind error"));
+        conn.error_level(ErrorLevel::Debug);
+        let rows = conn.result_sets("SELECT 2").unwrap().remove(0).into_rows();
+        assert_eq!(rows[0].get(0), Some("2"));
+    }
+
+    #[test]
+    fn query_one() {
+        let conn = prepare();
+        let row = conn
+            .query_one("SELECT name, age FROM users WHERE name = 'Bob'")
+            .unwrap();
+        assert_eq!(row.get_into::<_, i32>("age"), Ok(69));
+        assert_eq!(
+            conn.query_one("SELECT name FROM users WHERE age < 0"),
+            Err(Error::NoRows)
+        );
+        assert_eq!(
+            conn.query_one("SELECT name FROM users"),
+            Err(Error::TooManyRows)
+        );
+        assert!(conn.query_one("SELECT * FROM no_such_table").is_err());
+
+        let age = 42;
+        let row = conn
+            .query_opt(query!("SELECT name FROM users WHERE age = {age}"))
+            .unwrap();
+        assert_eq!(row.unwrap().get("name"), Some("Alice"));
+        assert_eq!(
+            conn.query_opt("SELECT name FROM users WHERE age < 0"),
+            Ok(None)
+        );
+        assert_eq!(
+            conn.query_opt("SELECT name FROM users"),
+            Err(Error::TooManyRows)
+        );
+        assert!(conn.query_opt("SELECT * FROM no_such_table").is_err());
+    }
+
+    #[test]
+    fn owned_rows() {
+        let conn = prepare();
+        let rows = conn.rows("SELECT name, age FROM users").unwrap();
+        let row = rows[1].clone();
+        drop(rows);
+        drop(conn);
+
+        let row = std::thread::spawn(move || {
+            assert_eq!(row.get("name"), Some("Bob"));
+            row
+        })
+        .join()
+        .unwrap();
+        assert_eq!(row.get_into::<_, i32>("age"), Ok(69));
+        assert_eq!(row.column_names(), ["name", "age"]);
+    }
+
+    #[test]
+    fn iterate_rows() {
+        let conn = prepare();
+        let mut names = Vec::new();
+        conn.iterate_rows("SELECT name, age FROM users ORDER BY age", |row| {
+            assert_eq!(row.column_count(), 2);
+            names.push(row.get("name").unwrap().to_string());
+            names.len() < 2
+        })
+        .unwrap();
+        assert_eq!(names, ["Alice", "Carol"]);
+
+        let mut calls = 0;
+        conn.iterate("SELECT name FROM users", |_| {
+            calls += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(conn.rows("SELECT name FROM users").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn execute_with_result() {
+        let conn = prepare();
+        let result = conn
+            .execute_with_result("INSERT INTO users VALUES ('Dave', 7)")
+            .unwrap();
+        assert_eq!(result.rows_affected(), 1);
+        assert_eq!(result.last_insert_id(), Some(4));
+
+        let age = 45;
+        let result = conn
+            .execute_with_result(query!("UPDATE users SET age = age + 1 WHERE age > {age}"))
+            .unwrap();
+        assert_eq!(result.rows_affected(), 2);
+        assert_eq!(result.last_insert_id(), None);
+
+        let result = conn
+            .execute_with_result("DELETE FROM users WHERE age < 0")
+            .unwrap();
+        assert_eq!(result.rows_affected(), 0);
+        let result = conn.execute_with_result("SELECT * FROM users").unwrap();
+        assert_eq!(result, ExecResult::default());
+        assert!(conn
+            .execute_with_result("INSERT INTO no_such_table VALUES (1)")
+            .is_err());
+
+        // The connection keeps the id of its last insert.
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT last_insert_rowid()"),
+            Ok(4)
+        );
+        conn.execute("INSERT INTO users VALUES (last_insert_rowid(), 0)")
+            .unwrap();
+        assert_eq!(
+            conn.query_scalar::<String>("SELECT name FROM users WHERE rowid = 5"),
+            Ok("4".to_string())
+        );
+
+        // SQLite counts the last statement of a batch that changed rows.
+        let result = conn
+            .execute_with_result("INSERT INTO users VALUES ('Eve', 1); SELECT 1;")
+            .unwrap();
+        assert_eq!(result.rows_affected(), 1);
+        assert_eq!(result.last_insert_id(), Some(6));
+    }
+
+    #[test]
+    fn transaction() {
+        let conn = prepare();
+        let count = |conn: &concatsql::Connection| {
+            conn.query_scalar::<i64>("SELECT count(*) FROM users")
+                .unwrap()
+        };
+
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO users VALUES ('Dave', 7)").unwrap();
+        assert!(conn.transaction().is_err());
+        tx.commit().unwrap();
+        assert_eq!(count(&conn), 4);
+
+        {
+            let tx = conn.transaction().unwrap();
+            tx.execute("DELETE FROM users").unwrap();
+            assert_eq!(count(&tx), 0);
+        }
+        assert_eq!(count(&conn), 4);
+
+        let insert = |conn: &concatsql::Connection| -> Result<(), Error> {
+            let tx = conn.transaction()?;
+            tx.execute("INSERT INTO users VALUES ('Eve', 8)")?;
+            tx.execute("INSERT INTO no_such_table VALUES (1)")?;
+            tx.commit()
+        };
+        assert!(insert(&conn).is_err());
+        assert_eq!(count(&conn), 4);
+
+        let mut tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO users VALUES ('Eve', 8)").unwrap();
+        {
+            let mut sp = tx.savepoint().unwrap();
+            sp.execute("INSERT INTO users VALUES ('Frank', 9)").unwrap();
+            let sp2 = sp.savepoint().unwrap();
+            sp2.execute("DELETE FROM users").unwrap();
+            sp2.rollback().unwrap();
+            sp.commit().unwrap();
+        }
+        {
+            let sp = tx.savepoint().unwrap();
+            sp.execute("DELETE FROM users").unwrap();
+        }
+        tx.commit().unwrap();
+        assert_eq!(count(&conn), 6);
+
+        let tx = conn
+            .build_transaction()
+            .isolation_level(IsolationLevel::Serializable)
+            .read_only(true)
+            .start()
+            .unwrap();
+        assert!(tx.execute("DELETE FROM users").is_err());
+        drop(tx);
+        conn.execute("DELETE FROM users WHERE age < 10").unwrap();
+        assert_eq!(count(&conn), 3);
+
+        // A failed COMMIT is reported whatever the error level, and rolls back.
+        conn.execute(
+            "PRAGMA foreign_keys = ON;
+             CREATE TABLE owners (name TEXT PRIMARY KEY);
+             CREATE TABLE pets (owner TEXT REFERENCES owners DEFERRABLE INITIALLY DEFERRED);",
+        )
+        .unwrap();
+        conn.error_level(ErrorLevel::AlwaysOk);
+        let tx = conn.transaction().unwrap();
+        tx.execute("INSERT INTO pets VALUES ('Nobody')").unwrap();
+        assert!(tx.commit().is_err());
+        conn.error_level(ErrorLevel::Debug);
+        assert_eq!(conn.query_scalar::<i64>("SELECT count(*) FROM pets"), Ok(0));
+        assert!(conn.transaction().is_ok());
+    }
+
+    #[test]
+    fn statement_cache() {
+        let conn = prepare();
+        for age in 0..3 {
+            let name = format!("User{}", age);
+            conn.execute(query!("INSERT INTO users VALUES ({name}, {age})"))
+                .unwrap();
+        }
+        let age = 10;
+        let sql = query!("SELECT * FROM users WHERE age < {age}");
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+
+        // The same statement is prepared again while the cached one is in use.
+        let mut rows = conn.query_iter(&sql);
+        assert!(rows.next().unwrap().is_ok());
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        assert_eq!(rows.count(), 2);
+
+        conn.execute("ALTER TABLE users ADD COLUMN note TEXT")
+            .unwrap();
+        assert_eq!(conn.rows(&sql).unwrap()[0].column_count(), 3);
+        assert_eq!(
+            conn.query_iter(&sql)
+                .next()
+                .unwrap()
+                .unwrap()
+                .column_count(),
+            3
+        );
+
+        conn.set_statement_cache_capacity(0);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        conn.set_statement_cache_capacity(1);
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+        assert_eq!(conn.query_scalar::<i64>(query!("SELECT {age}")), Ok(10));
+        conn.clear_statement_cache();
+        assert_eq!(conn.rows(&sql).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn prepared_statement() {
+        let conn = prepare();
+        let (name, age) = ("", 0);
+        let insert = conn
+            .prepare(query!("INSERT INTO users VALUES ({name}, {age})"))
+            .unwrap();
+        assert_eq!(insert.param_count(), 2);
+        insert.execute(params!["Dave", 20]).unwrap();
+        assert_eq!(
+            insert
+                .execute_with_result(params!["Eve", 30])
+                .unwrap()
+                .rows_affected(),
+            1
+        );
+        assert!(insert.execute(params!["Frank"]).is_err());
+        assert!(insert.execute(params!["Frank", 1, 2]).is_err());
+
+        let select = conn
+            .prepare("SELECT name FROM users WHERE age < ? ORDER BY age")
+            .unwrap();
+        assert_eq!(select.param_count(), 1);
+        let rows = select.rows(params![35]).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("name"), Some("Dave"));
+        assert_eq!(select.query_iter(params![25]).count(), 1);
+        assert!(select.query_iter(&[]).next().unwrap().is_err());
+
+        // A statement stays prepared whatever the capacity and the queries run in between.
+        conn.set_statement_cache_capacity(0);
+        for age in 0..40 {
+            conn.rows(query!("SELECT {age}")).unwrap();
+        }
+        assert_eq!(select.rows(params![35]).unwrap().len(), 2);
+        insert.execute(params!["Frank", 40]).unwrap();
+
+        // Without placeholders, it is still run as a single prepared statement.
+        let delete = conn
+            .prepare("DELETE FROM users WHERE name = 'Frank'; DELETE FROM users")
+            .unwrap();
+        assert_eq!(delete.param_count(), 0);
+        assert_eq!(delete.execute_with_result(&[]).unwrap().rows_affected(), 1);
+        assert_eq!(select.rows(params![35]).unwrap().len(), 2);
+
+        assert!(conn.prepare("SELECT * FROM missing").is_err());
+    }
+
+    #[test]
+    fn execute_many() {
+        let conn = prepare();
+        let users = [("Dave".to_string(), 20), ("Eve".to_string(), 30)];
+        let rows = users
+            .iter()
+            .map(|(name, age)| vec![name as &dyn ToValue, age]);
+        assert_eq!(
+            conn.execute_many("INSERT INTO users VALUES (?, ?)", rows, false),
+            Ok(2)
+        );
+
+        let (name, age) = ("", 0);
+        let sql = query!("UPDATE users SET age = {age} WHERE name = {name}");
+        assert_eq!(
+            conn.execute_many(&sql, vec![params![1, "Dave"], params![2, "Nobody"]], false),
+            Ok(1)
+        );
+
+        // In its own transaction, a bad parameter set rolls back the whole batch,
+        // even if errors are ignored.
+        let sql = query!("INSERT INTO users VALUES ({name}, {age})");
+        assert!(conn
+            .execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], true)
+            .is_err());
+        conn.error_level(ErrorLevel::AlwaysOk);
+        assert_eq!(
+            conn.execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], true),
+            Ok(0)
+        );
+        conn.error_level(ErrorLevel::Debug);
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+
+        // Otherwise the sets before the bad one are applied.
+        assert!(conn
+            .execute_many(&sql, vec![params!["Frank", 40], params!["Grace"]], false)
+            .is_err());
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(6)
+        );
+        conn.execute("DELETE FROM users WHERE name = 'Frank'")
+            .unwrap();
+
+        // Inside a transaction, the batch is part of it.
+        let tx = conn.transaction().unwrap();
+        assert!(tx
+            .execute_many(&sql, vec![params!["Frank", 40]], true)
+            .is_err());
+        assert_eq!(
+            tx.execute_many(&sql, vec![params!["Frank", 40]], false),
+            Ok(1)
+        );
+        tx.rollback().unwrap();
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+
+        // So is one begun with plain SQL.
+        conn.execute("BEGIN").unwrap();
+        assert_eq!(
+            conn.execute_many(&sql, vec![params!["Frank", 40]], false),
+            Ok(1)
+        );
+        conn.execute("ROLLBACK").unwrap();
+        assert_eq!(
+            conn.query_scalar::<i64>("SELECT count(*) FROM users"),
+            Ok(5)
+        );
+    }
+
     #[test]
     fn question() {
         let conn = prepare();
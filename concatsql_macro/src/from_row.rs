@@ -0,0 +1,110 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{Data, DeriveInput, Field, Fields, LitStr};
+
+/// How a field is read from the row.
+enum Source {
+    Column { name: LitStr, default: bool },
+    Flatten,
+}
+
+pub(crate) fn derive(input: DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => abort!(
+                input,
+                "`FromRow` can only be derived for structs with named fields"
+            ),
+        },
+        _ => abort!(input, "`FromRow` can only be derived for structs"),
+    };
+
+    let fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        match source(field) {
+            Source::Column {
+                name,
+                default: false,
+            } => quote! {
+                #ident: row.get_into::<_, #ty>(#name)?
+            },
+            Source::Column {
+                name,
+                default: true,
+            } => quote! {
+                #ident: match row.get_value(#name) {
+                    ::std::option::Option::None
+                    | ::std::option::Option::Some(::concatsql::Value::Null) => {
+                        ::std::default::Default::default()
+                    }
+                    ::std::option::Option::Some(_) => row.get_into::<_, #ty>(#name)?,
+                }
+            },
+            Source::Flatten => quote! {
+                #ident: <#ty as ::concatsql::FromRow>::from_row(row)?
+            },
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::concatsql::FromRow for #name #ty_generics #where_clause {
+            fn from_row(
+                row: &::concatsql::Row,
+            ) -> ::std::result::Result<Self, ::concatsql::Error> {
+                ::std::result::Result::Ok(Self {
+                    #(#fields,)*
+                })
+            }
+        }
+    }
+}
+
+fn source(field: &Field) -> Source {
+    let mut rename = None;
+    let mut default = false;
+    let mut flatten = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("concatsql"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else if meta.path.is_ident("flatten") {
+                flatten = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename`, `default` or `flatten`"))
+            }
+        });
+        if let Err(e) = result {
+            abort!(e.span(), "{}", e);
+        }
+    }
+
+    if flatten {
+        if rename.is_some() || default {
+            abort!(
+                field,
+                "`flatten` cannot be combined with `rename` or `default`"
+            );
+        }
+        return Source::Flatten;
+    }
+    let ident = field.ident.as_ref().unwrap();
+    Source::Column {
+        name: rename.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span())),
+        default,
+    }
+}
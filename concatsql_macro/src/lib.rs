@@ -1,5 +1,8 @@
 extern crate proc_macro;
 
+mod from_row;
+mod to_value;
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,7 +14,7 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use proc_macro_error::{abort_call_site, proc_macro_error};
 use quote::quote;
-use syn::LitStr;
+use syn::{DeriveInput, LitStr};
 
 #[derive(Debug)]
 enum Query {
@@ -137,3 +140,89 @@ pub fn query(item: TokenStream) -> TokenStream {
         Err(e) => abort_call_site!("{}", e),
     }
 }
+
+/// Derive `ToValue` for newtypes and fieldless enums.
+///
+/// A struct with exactly one field is bound as that field.
+/// A fieldless enum is bound as the variant name by default, or as its discriminant
+/// with `#[concatsql(integer)]`. A variant can be renamed with `#[concatsql(rename = "...")]`.
+///
+/// Like any `ToValue` type, the derived type can be concatenated to a `WrapString`.
+///
+/// # Examples
+///
+/// ```
+/// use concatsql::prelude::*;
+///
+/// #[derive(ToValue)]
+/// struct UserId(i64);
+///
+/// #[derive(ToValue)]
+/// enum Role {
+///     Admin,
+///     #[concatsql(rename = "guest")]
+///     Guest,
+/// }
+///
+/// #[derive(ToValue)]
+/// #[concatsql(integer)]
+/// enum Level {
+///     Low = 1,
+///     High = 10,
+/// }
+///
+/// let id = UserId(42);
+/// let role = Role::Guest;
+/// let level = Level::High;
+/// let sql = query!("SELECT * FROM users WHERE id = {id} AND role = {role} AND level = {level}");
+/// assert_eq!(sql.simulate(), "SELECT * FROM users WHERE id = 42 AND role = 'guest' AND level = 10");
+///
+/// let sql = query!("SELECT * FROM users WHERE role = ") + Role::Admin;
+/// assert_eq!(sql.simulate(), "SELECT * FROM users WHERE role = 'Admin'");
+/// ```
+#[proc_macro_derive(ToValue, attributes(concatsql))]
+#[proc_macro_error]
+pub fn derive_to_value(item: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse_macro_input!(item);
+    to_value::derive(input).into()
+}
+
+/// Derive `FromRow` for structs with named fields.
+///
+/// Each field is read with `Row::get_into` from the column of the same name.
+///
+/// * `#[concatsql(rename = "...")]` reads from another column.
+/// * `#[concatsql(default)]` uses `Default::default()` when the column is missing or NULL.
+/// * `#[concatsql(flatten)]` reads the field, itself a `FromRow`, from the same row.
+///
+/// # Examples
+///
+/// ```
+/// use concatsql::prelude::*;
+///
+/// #[derive(FromRow)]
+/// struct Audit {
+///     created_by: String,
+/// }
+///
+/// #[derive(FromRow)]
+/// struct User {
+///     id: i64,
+///     #[concatsql(rename = "user_name")]
+///     name: String,
+///     #[concatsql(default)]
+///     age: u32,
+///     #[concatsql(flatten)]
+///     audit: Audit,
+/// }
+///
+/// fn users(conn: &Connection) -> concatsql::Result<Vec<User>> {
+///     conn.rows_as::<User>("SELECT id, user_name, age, created_by FROM users")
+/// }
+/// ```
+#[proc_macro_derive(FromRow, attributes(concatsql))]
+#[proc_macro_error]
+pub fn derive_from_row(item: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse_macro_input!(item);
+    from_row::derive(input).into()
+}
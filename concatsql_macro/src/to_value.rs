@@ -0,0 +1,145 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields, LitStr, Type};
+
+/// How a fieldless enum is sent to the database.
+enum Repr {
+    Text,
+    Integer,
+}
+
+pub(crate) fn derive(input: DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('__concatsql));
+
+    let methods = match &input.data {
+        Data::Struct(data) => {
+            let (field, ty) = newtype(&data.fields);
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::concatsql::ToValue<'__concatsql>));
+            quote! {
+                fn to_value(&self) -> ::concatsql::Value<'__concatsql> {
+                    ::concatsql::ToValue::to_value(&self.#field)
+                }
+                fn into_value(self) -> ::concatsql::Value<'__concatsql> {
+                    ::concatsql::ToValue::into_value(self.#field)
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let repr = enum_repr(&input);
+            let arms = data.variants.iter().map(|variant| {
+                if !matches!(variant.fields, Fields::Unit) {
+                    abort!(variant, "`ToValue` can only be derived for fieldless enums");
+                }
+                let ident = &variant.ident;
+                match repr {
+                    Repr::Text => {
+                        let text = variant_rename(variant)
+                            .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+                        quote! {
+                            #name::#ident => ::concatsql::Value::Text(
+                                ::std::borrow::Cow::Borrowed(#text)
+                            ),
+                        }
+                    }
+                    Repr::Integer => quote! {
+                        #name::#ident => ::concatsql::Value::I64(#name::#ident as i64),
+                    },
+                }
+            });
+            quote! {
+                fn to_value(&self) -> ::concatsql::Value<'__concatsql> {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(_) => abort!(input, "`ToValue` cannot be derived for unions"),
+    };
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::concatsql::ToValue<'__concatsql> for #name #ty_generics #where_clause {
+            #methods
+        }
+
+        impl #impl_generics ::concatsql::ToValue<'__concatsql> for &#name #ty_generics #where_clause {
+            fn to_value(&self) -> ::concatsql::Value<'__concatsql> {
+                ::concatsql::ToValue::to_value(*self)
+            }
+        }
+    }
+}
+
+/// Returns the accessor and type of the only field.
+fn newtype(fields: &Fields) -> (TokenStream, &Type) {
+    match fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let index = syn::Index::from(0);
+            (quote!(#index), &fields.unnamed[0].ty)
+        }
+        Fields::Named(fields) if fields.named.len() == 1 => {
+            let ident = &fields.named[0].ident;
+            (quote!(#ident), &fields.named[0].ty)
+        }
+        _ => abort!(
+            fields,
+            "`ToValue` can only be derived for structs with exactly one field"
+        ),
+    }
+}
+
+fn enum_repr(input: &DeriveInput) -> Repr {
+    let mut repr = Repr::Text;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("concatsql"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("text") {
+                repr = Repr::Text;
+                Ok(())
+            } else if meta.path.is_ident("integer") {
+                repr = Repr::Integer;
+                Ok(())
+            } else {
+                Err(meta.error("expected `text` or `integer`"))
+            }
+        });
+        if let Err(e) = result {
+            abort!(e.span(), "{}", e);
+        }
+    }
+    repr
+}
+
+fn variant_rename(variant: &syn::Variant) -> Option<LitStr> {
+    let mut rename = None;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("concatsql"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename`"))
+            }
+        });
+        if let Err(e) = result {
+            abort!(e.span(), "{}", e);
+        }
+    }
+    rename
+}
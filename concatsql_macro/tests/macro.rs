@@ -33,6 +33,53 @@ mod macros {
         assert_eq!(sql.simulate(), "1234'5'");
     }
 
+    #[test]
+    fn derive_to_value_newtype_test() {
+        #[derive(ToValue)]
+        struct UserId(i64);
+        #[derive(ToValue)]
+        struct Name {
+            inner: String,
+        }
+        #[derive(ToValue)]
+        struct Wrapper<T>(T);
+
+        let id = UserId(42);
+        let name = Name {
+            inner: "Alice".to_string(),
+        };
+        let sql = query!("{id}{name}");
+        assert_eq!(sql.simulate(), "42'Alice'");
+        let sql = query!("") + &id + id + query!(",") + Wrapper(1.5f64) + Wrapper("O'Reilly");
+        assert_eq!(sql.simulate(), "4242,1.5'O''Reilly'");
+        let sql = query!("") + params![UserId(1), Wrapper(()), name];
+        assert_eq!(sql.simulate(), "1,NULL,'Alice'");
+    }
+
+    #[test]
+    fn derive_to_value_enum_test() {
+        #[derive(ToValue)]
+        enum Role {
+            Admin,
+            #[concatsql(rename = "guest")]
+            Guest,
+        }
+        #[derive(ToValue)]
+        #[concatsql(integer)]
+        enum Level {
+            Low = -1,
+            Middle,
+            High = 10,
+        }
+
+        let role = Role::Admin;
+        let level = Level::Low;
+        let sql = query!("{role}{level}");
+        assert_eq!(sql.simulate(), "'Admin'-1");
+        let sql = query!("") + Role::Guest + &Level::Middle + Level::High;
+        assert_eq!(sql.simulate(), "'guest'010");
+    }
+
     //#[test]
     //fn query_compile_error_invalid_format_test() {
     //    let foo = 1;